@@ -1,26 +1,73 @@
-use std::io::{ErrorKind, Result};
+use std::{
+    future::{poll_fn, Future},
+    io::{ErrorKind, Result},
+    os::fd::{AsFd, AsRawFd, RawFd},
+    pin::pin,
+    ptr,
+    task::Poll,
+};
 
+use crate::{
+    runtime::driver::{driver, Driver, READABLE, WRITABLE},
+    sys::{cvt, is_fifo, is_unsupported, pipe},
+};
+
+pub mod codec;
+
+mod async_fd;
+mod buf_read;
+mod buf_reader;
+mod buf_writer;
 mod chain;
+mod counted;
 mod empty;
+mod inspect;
+mod interest;
+mod lines;
 mod read;
+mod ready;
 mod repeat;
 mod sink;
+mod split;
+mod stderr;
+mod stdin;
+mod stdio;
+mod stdout;
 mod take;
+mod tee;
+mod throttle;
+mod timeout;
 mod write;
 
+pub use async_fd::*;
+pub use buf_read::*;
+pub use buf_reader::*;
+pub use buf_writer::*;
 pub use chain::*;
+pub use counted::*;
 pub use empty::*;
+pub use inspect::*;
+pub use interest::*;
+pub use lines::*;
 pub use read::*;
+pub use ready::*;
 pub use repeat::*;
 pub use sink::*;
+pub use split::*;
+pub use stderr::*;
+pub use stdin::*;
+pub use stdout::*;
 pub use take::*;
+pub use tee::*;
+pub use throttle::*;
+pub use timeout::*;
 pub use write::*;
 
 pub mod prelude {
-    pub use super::{AsyncRead, AsyncWrite};
+    pub use super::{AsyncBufRead, AsyncRead, AsyncWrite};
 }
 
-pub(self) const INIT_BUFFER_SIZE: usize = 4096;
+pub(crate) const INIT_BUFFER_SIZE: usize = 4096;
 
 pub async fn copy<R, W>(reader: &mut R, writer: &mut W) -> Result<u64>
 where
@@ -57,6 +104,220 @@ where
     }
 }
 
+pub async fn copy_buf<R, W>(reader: &mut R, writer: &mut W) -> Result<u64>
+where
+    R: AsyncBufRead + ?Sized,
+    W: AsyncWrite + ?Sized,
+{
+    let mut length = 0;
+
+    loop {
+        let buffer = match reader.fill_buf().await {
+            Ok(buffer) => buffer,
+            Err(error) => match error.kind() {
+                ErrorKind::Interrupted => continue,
+                _ => return Err(error),
+            },
+        };
+
+        if buffer.is_empty() {
+            return Ok(length);
+        }
+
+        let mut length_ = 0;
+
+        while length_ != buffer.len() {
+            match writer.write(&buffer[length_..]).await {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(written) => length_ += written,
+                Err(error) => match error.kind() {
+                    ErrorKind::Interrupted => {}
+                    _ => return Err(error),
+                },
+            }
+        }
+
+        reader.consume(length_);
+        length += length_ as u64;
+    }
+}
+
+pub async fn copy_bidirectional<A, B>(a: &A, b: &B) -> Result<(u64, u64)>
+where
+    A: ?Sized,
+    B: ?Sized,
+    for<'a> &'a A: AsyncRead + AsyncWrite,
+    for<'b> &'b B: AsyncRead + AsyncWrite,
+{
+    let mut a_to_b = pin!(copy_half(a, b));
+    let mut b_to_a = pin!(copy_half(b, a));
+    let mut a_to_b_length = None;
+    let mut b_to_a_length = None;
+
+    poll_fn(|context| {
+        if a_to_b_length.is_none() {
+            if let Poll::Ready(length) = a_to_b.as_mut().poll(context) {
+                a_to_b_length = Some(length?);
+            }
+        }
+
+        if b_to_a_length.is_none() {
+            if let Poll::Ready(length) = b_to_a.as_mut().poll(context) {
+                b_to_a_length = Some(length?);
+            }
+        }
+
+        match (a_to_b_length, b_to_a_length) {
+            (Some(a_to_b_length), Some(b_to_a_length)) => {
+                Poll::Ready(Ok((a_to_b_length, b_to_a_length)))
+            }
+            _ => Poll::Pending,
+        }
+    })
+    .await
+}
+
+async fn copy_half<R: AsyncRead, W: AsyncWrite>(mut reader: R, mut writer: W) -> Result<u64> {
+    let length = copy(&mut reader, &mut writer).await?;
+
+    writer.shutdown().await?;
+
+    Ok(length)
+}
+
+pub async fn splice(from: impl AsFd, to: impl AsFd, len: usize) -> Result<usize> {
+    let from = from.as_fd().as_raw_fd();
+    let to = to.as_fd().as_raw_fd();
+
+    let spliced = if is_fifo(from)? || is_fifo(to)? {
+        splice_direct(from, to, len).await?
+    } else {
+        splice_through_pipe(from, to, len).await?
+    };
+
+    match spliced {
+        Some(length) => Ok(length),
+        None => splice_buffered(from, to, len).await,
+    }
+}
+
+async fn splice_direct(from: RawFd, to: RawFd, len: usize) -> Result<Option<usize>> {
+    let mut total = 0;
+
+    while total < len {
+        match splice_fd(from, to, len - total).await {
+            Ok(0) => break,
+            Ok(length) => total += length,
+            Err(error) if total == 0 && is_unsupported(&error) => return Ok(None),
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(Some(total))
+}
+
+async fn splice_through_pipe(from: RawFd, to: RawFd, len: usize) -> Result<Option<usize>> {
+    let (pipe_reader, pipe_writer) = pipe()?;
+    let mut total = 0;
+
+    while total < len {
+        let length = match splice_fd(from, pipe_writer.as_raw_fd(), len - total).await {
+            Ok(0) => break,
+            Ok(length) => length,
+            Err(error) if total == 0 && is_unsupported(&error) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let mut drained = 0;
+
+        while drained < length {
+            match splice_fd(pipe_reader.as_raw_fd(), to, length - drained).await {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(length) => drained += length,
+                Err(error) if total == 0 && drained == 0 && is_unsupported(&error) => {
+                    splice_buffered(pipe_reader.as_raw_fd(), to, length).await?;
+
+                    return Ok(Some(
+                        length + splice_buffered(from, to, len - length).await?,
+                    ));
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        total += length;
+    }
+
+    Ok(Some(total))
+}
+
+async fn splice_fd(from: RawFd, to: RawFd, len: usize) -> Result<usize> {
+    loop {
+        match cvt(unsafe {
+            libc::splice(
+                from,
+                ptr::null_mut(),
+                to,
+                ptr::null_mut(),
+                len,
+                libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+            )
+        }) {
+            Ok(length) => return Ok(length as usize),
+            Err(error)
+                if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::Interrupted) =>
+            {
+                driver().clear_ready(from, READABLE);
+                driver().clear_ready(to, WRITABLE);
+                driver().ready(from, READABLE).await?;
+                driver().ready(to, WRITABLE).await?;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+async fn splice_buffered(from: RawFd, to: RawFd, len: usize) -> Result<usize> {
+    let mut buffer = [0u8; INIT_BUFFER_SIZE];
+    let mut total = 0;
+
+    while total < len {
+        let buffer = &mut buffer[..(len - total).min(INIT_BUFFER_SIZE)];
+        let length = driver()
+            .io(from, READABLE, || {
+                cvt(unsafe {
+                    libc::read(from, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len())
+                })
+            })
+            .await? as usize;
+
+        if length == 0 {
+            break;
+        }
+
+        let mut written = 0;
+
+        while written < length {
+            let buffer = &buffer[written..length];
+
+            match driver()
+                .io(to, WRITABLE, || {
+                    cvt(unsafe {
+                        libc::write(to, buffer.as_ptr() as *const libc::c_void, buffer.len())
+                    })
+                })
+                .await?
+            {
+                0 => return Err(ErrorKind::WriteZero.into()),
+                length => written += length as usize,
+            }
+        }
+
+        total += length;
+    }
+
+    Ok(total)
+}
+
 pub const fn empty() -> Empty {
     Empty
 }
@@ -76,3 +337,81 @@ pub const fn repeat(byte: u8) -> Repeat {
 pub const fn sink() -> Sink {
     Sink
 }
+
+pub fn stdin() -> Stdin {
+    Stdin::new()
+}
+
+pub fn stdout() -> Stdout {
+    Stdout::new()
+}
+
+pub fn stderr() -> Stderr {
+    Stderr::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_util::{block_on, tcp_pair, Mock},
+        thread::spawn,
+    };
+
+    #[test]
+    fn copy_buf_copies_every_chunk() {
+        block_on(async {
+            let mut reader = BufReader::with_capacity(4, Mock::new([&b"hello "[..], b"world"]));
+            let mut writer = Mock::default();
+
+            assert_eq!(copy_buf(&mut reader, &mut writer).await.unwrap(), 11);
+            assert_eq!(writer.output, b"hello world");
+        });
+    }
+
+    #[test]
+    fn copy_bidirectional_proxies_both_directions() {
+        block_on(async {
+            let (mut client, proxy_a) = tcp_pair().await;
+            let (proxy_b, mut server) = tcp_pair().await;
+            let proxy = spawn(async move { copy_bidirectional(&proxy_a, &proxy_b).await });
+            let mut received = Vec::new();
+
+            client.write_all(b"ping").await.unwrap();
+            AsyncWrite::shutdown(&mut client).await.unwrap();
+            server.read_to_end(&mut received).await.unwrap();
+            assert_eq!(received, b"ping");
+
+            server.write_all(b"pong!").await.unwrap();
+            AsyncWrite::shutdown(&mut server).await.unwrap();
+            received.clear();
+            client.read_to_end(&mut received).await.unwrap();
+            assert_eq!(received, b"pong!");
+
+            assert_eq!(proxy.await.unwrap(), (4, 5));
+        });
+    }
+
+    #[test]
+    fn splice_moves_bytes_between_sockets_and_pipes() {
+        block_on(async {
+            let (mut client, proxy_in) = tcp_pair().await;
+            let (proxy_out, mut server) = tcp_pair().await;
+            let (pipe_reader, pipe_writer) = pipe().unwrap();
+            let mut received = [0; 11];
+
+            client.write_all(b"hello world").await.unwrap();
+
+            assert_eq!(splice(&proxy_in, &proxy_out, 11).await.unwrap(), 11);
+            server.read_exact(&mut received).await.unwrap();
+            assert_eq!(&received, b"hello world");
+
+            client.write_all(b"pipe").await.unwrap();
+
+            assert_eq!(splice(&proxy_in, &pipe_writer, 4).await.unwrap(), 4);
+            assert_eq!(splice(&pipe_reader, &proxy_out, 4).await.unwrap(), 4);
+            server.read_exact(&mut received[..4]).await.unwrap();
+            assert_eq!(&received[..4], b"pipe");
+        });
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    error, fmt,
+    io::{Error, ErrorKind, Result},
+    os::fd::{AsRawFd, RawFd},
+};
+
+use super::{Interest, Ready};
+use crate::runtime::driver::{driver, Driver};
+
+pub struct AsyncFd<T: AsRawFd> {
+    inner: Option<T>,
+    fd: RawFd,
+    interest: Interest,
+    registered: bool,
+}
+
+impl<T: AsRawFd> AsyncFd<T> {
+    pub fn new(inner: T) -> Result<AsyncFd<T>> {
+        Self::with_interest(inner, Interest::READABLE | Interest::WRITABLE)
+    }
+
+    pub fn with_interest(inner: T, interest: Interest) -> Result<AsyncFd<T>> {
+        let fd = inner.as_raw_fd();
+
+        let registered = driver().register(fd, interest.events())?;
+
+        Ok(AsyncFd {
+            inner: Some(inner),
+            fd,
+            interest,
+            registered,
+        })
+    }
+
+    pub fn get_ref(&self) -> &T {
+        self.inner.as_ref().expect("AsyncFd inner is missing")
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner.as_mut().expect("AsyncFd inner is missing")
+    }
+
+    pub fn into_inner(mut self) -> T {
+        if self.registered {
+            driver().deregister(self.fd).ok();
+        }
+
+        self.inner.take().expect("AsyncFd inner is missing")
+    }
+
+    pub fn interest(&self) -> Interest {
+        self.interest
+    }
+
+    pub async fn ready(&self, interest: Interest) -> Result<AsyncFdReadyGuard<'_, T>> {
+        if interest.events() & !self.interest.events() != 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "AsyncFd isn't registered for this interest",
+            ));
+        }
+
+        let events = driver().wait_ready(self.fd, interest.events()).await?;
+
+        Ok(AsyncFdReadyGuard {
+            async_fd: self,
+            ready: Ready::from_events(events),
+        })
+    }
+
+    pub async fn readable(&self) -> Result<AsyncFdReadyGuard<'_, T>> {
+        self.ready(Interest::READABLE).await
+    }
+
+    pub async fn writable(&self) -> Result<AsyncFdReadyGuard<'_, T>> {
+        self.ready(Interest::WRITABLE).await
+    }
+
+    pub async fn async_io<R>(
+        &self,
+        interest: Interest,
+        mut f: impl FnMut(&T) -> Result<R>,
+    ) -> Result<R> {
+        loop {
+            let mut guard = self.ready(interest).await?;
+
+            if let Ok(result) = guard.try_io(|async_fd| f(async_fd.get_ref())) {
+                return result;
+            }
+        }
+    }
+}
+
+impl<T: AsRawFd> AsRawFd for AsyncFd<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl<T: AsRawFd + fmt::Debug> fmt::Debug for AsyncFd<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncFd")
+            .field("inner", &self.inner)
+            .field("interest", &self.interest)
+            .finish()
+    }
+}
+
+impl<T: AsRawFd> Drop for AsyncFd<T> {
+    fn drop(&mut self) {
+        if self.inner.is_some() && self.registered {
+            driver().deregister(self.fd).ok();
+        }
+    }
+}
+
+pub struct AsyncFdReadyGuard<'a, T: AsRawFd> {
+    async_fd: &'a AsyncFd<T>,
+    ready: Ready,
+}
+
+impl<'a, T: AsRawFd> AsyncFdReadyGuard<'a, T> {
+    pub fn ready(&self) -> Ready {
+        self.ready
+    }
+
+    pub fn clear_ready(&mut self) {
+        driver().clear_ready(self.async_fd.fd, self.ready.events());
+        self.ready = Ready::EMPTY;
+    }
+
+    pub fn clear_ready_matching(&mut self, ready: Ready) {
+        driver().clear_ready(self.async_fd.fd, (self.ready & ready).events());
+        self.ready = self.ready - ready;
+    }
+
+    pub fn get_ref(&self) -> &'a AsyncFd<T> {
+        self.async_fd
+    }
+
+    pub fn get_inner(&self) -> &'a T {
+        self.async_fd.get_ref()
+    }
+
+    pub fn try_io<R>(
+        &mut self,
+        f: impl FnOnce(&'a AsyncFd<T>) -> Result<R>,
+    ) -> std::result::Result<Result<R>, TryIoError> {
+        match f(self.async_fd) {
+            Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                self.clear_ready();
+
+                Err(TryIoError(()))
+            }
+            result => Ok(result),
+        }
+    }
+}
+
+impl<T: AsRawFd + fmt::Debug> fmt::Debug for AsyncFdReadyGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncFdReadyGuard")
+            .field("async_fd", &self.async_fd)
+            .field("ready", &self.ready)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+pub struct TryIoError(());
+
+impl fmt::Display for TryIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Operation would block")
+    }
+}
+
+impl error::Error for TryIoError {}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, os::unix::net::UnixStream};
+
+    use super::*;
+    use crate::{
+        io::AsyncWrite,
+        runtime::driver::READABLE,
+        test_util::{block_on, tcp_pair},
+    };
+
+    #[test]
+    fn waits_only_for_the_registered_interest() {
+        block_on(async {
+            let (stream, mut peer) = UnixStream::pair().unwrap();
+
+            stream.set_nonblocking(true).unwrap();
+
+            let async_fd = AsyncFd::with_interest(stream, Interest::READABLE).unwrap();
+
+            assert_eq!(
+                async_fd.writable().await.unwrap_err().kind(),
+                ErrorKind::InvalidInput
+            );
+
+            peer.write_all(b"ping").unwrap();
+
+            let mut buffer = [0; 8];
+            let length = async_fd
+                .async_io(Interest::READABLE, |mut stream| {
+                    std::io::Read::read(&mut stream, &mut buffer)
+                })
+                .await
+                .unwrap();
+
+            assert_eq!(&buffer[..length], b"ping");
+        });
+    }
+
+    #[test]
+    fn leaves_registrations_it_does_not_own() {
+        block_on(async {
+            let (mut client, server) = tcp_pair().await;
+            let fd = server.as_raw_fd();
+
+            client.write_all(b"ping").await.unwrap();
+            driver().ready(fd, READABLE).await.unwrap();
+
+            let async_fd = AsyncFd::new(fd).unwrap();
+
+            assert!(async_fd.readable().await.unwrap().ready().is_readable());
+            drop(async_fd);
+            assert!(driver().is_tracked(fd));
+        });
+    }
+}
@@ -0,0 +1,70 @@
+use std::{future::Future, io};
+
+use super::{AsyncRead, Lines};
+
+pub trait AsyncBufRead: AsyncRead {
+    fn fill_buf(&mut self) -> impl Future<Output = io::Result<&[u8]>>;
+
+    fn consume(&mut self, amt: usize);
+
+    fn read_until(
+        &mut self,
+        byte: u8,
+        buf: &mut Vec<u8>,
+    ) -> impl Future<Output = io::Result<usize>> {
+        async move {
+            let mut total = 0;
+
+            loop {
+                let (done, length) = {
+                    let available = match self.fill_buf().await {
+                        Ok(available) => available,
+                        Err(error) => match error.kind() {
+                            io::ErrorKind::Interrupted => continue,
+                            _ => return Err(error),
+                        },
+                    };
+
+                    match available.iter().position(|byte_| *byte_ == byte) {
+                        Some(index) => {
+                            buf.extend_from_slice(&available[..=index]);
+
+                            (true, index + 1)
+                        }
+                        None => {
+                            buf.extend_from_slice(available);
+
+                            (available.is_empty(), available.len())
+                        }
+                    }
+                };
+
+                self.consume(length);
+                total += length;
+
+                if done {
+                    break Ok(total);
+                }
+            }
+        }
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> impl Future<Output = io::Result<usize>> {
+        async {
+            let mut buffer = Vec::new();
+            let length = self.read_until(b'\n', &mut buffer).await?;
+
+            *buf += &String::from_utf8(buffer)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+            Ok(length)
+        }
+    }
+
+    fn lines(self) -> Lines<Self>
+    where
+        Self: Sized,
+    {
+        Lines::new(self)
+    }
+}
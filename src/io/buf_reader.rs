@@ -0,0 +1,93 @@
+use std::{fmt, io};
+
+use super::{AsyncBufRead, AsyncRead, INIT_BUFFER_SIZE};
+
+pub struct BufReader<R> {
+    reader: R,
+    buffer: Box<[u8]>,
+    position: usize,
+    filled: usize,
+}
+
+impl<R: AsyncRead> BufReader<R> {
+    pub fn new(reader: R) -> BufReader<R> {
+        Self::with_capacity(INIT_BUFFER_SIZE, reader)
+    }
+
+    pub fn with_capacity(capacity: usize, reader: R) -> BufReader<R> {
+        BufReader {
+            reader,
+            buffer: vec![0; capacity].into_boxed_slice(),
+            position: 0,
+            filled: 0,
+        }
+    }
+}
+
+impl<R> BufReader<R> {
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer[self.position..self.filled]
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for BufReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufReader")
+            .field("reader", &self.reader)
+            .field(
+                "buffer",
+                &format_args!("{}/{}", self.filled - self.position, self.capacity()),
+            )
+            .finish()
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for BufReader<R> {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.filled && buf.len() >= self.capacity() {
+            self.position = 0;
+            self.filled = 0;
+
+            return self.reader.read(buf).await;
+        }
+
+        let available = self.fill_buf().await?;
+        let length = available.len().min(buf.len());
+
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Ok(length)
+    }
+}
+
+impl<R: AsyncRead> AsyncBufRead for BufReader<R> {
+    async fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position >= self.filled {
+            self.filled = self.reader.read(&mut self.buffer).await?;
+            self.position = 0;
+        }
+
+        Ok(&self.buffer[self.position..self.filled])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position = (self.position + amt).min(self.filled);
+    }
+}
@@ -0,0 +1,108 @@
+use std::{fmt, io};
+
+use super::{AsyncWrite, INIT_BUFFER_SIZE};
+
+pub struct BufWriter<W> {
+    writer: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: AsyncWrite> BufWriter<W> {
+    pub fn new(writer: W) -> BufWriter<W> {
+        Self::with_capacity(INIT_BUFFER_SIZE, writer)
+    }
+
+    pub fn with_capacity(capacity: usize, writer: W) -> BufWriter<W> {
+        BufWriter {
+            writer,
+            buffer: Vec::with_capacity(capacity),
+        }
+    }
+
+    async fn flush_buffer(&mut self) -> io::Result<()> {
+        let mut length = 0;
+
+        while length != self.buffer.len() {
+            match self.writer.write(&self.buffer[length..]).await {
+                Ok(0) => {
+                    self.buffer.drain(..length);
+
+                    return Err(io::ErrorKind::WriteZero.into());
+                }
+                Ok(length_) => length += length_,
+                Err(error) => match error.kind() {
+                    io::ErrorKind::Interrupted => {}
+                    _ => {
+                        self.buffer.drain(..length);
+
+                        return Err(error);
+                    }
+                },
+            }
+        }
+
+        self.buffer.clear();
+
+        Ok(())
+    }
+}
+
+impl<W> BufWriter<W> {
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for BufWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufWriter")
+            .field("writer", &self.writer)
+            .field(
+                "buffer",
+                &format_args!("{}/{}", self.buffer.len(), self.capacity()),
+            )
+            .finish()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for BufWriter<W> {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buffer.len() + buf.len() > self.capacity() {
+            self.flush_buffer().await?;
+        }
+
+        if buf.len() >= self.capacity() {
+            self.writer.write(buf).await
+        } else {
+            self.buffer.extend_from_slice(buf);
+
+            Ok(buf.len())
+        }
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.flush_buffer().await?;
+        self.writer.flush().await
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        self.flush_buffer().await?;
+        self.writer.shutdown().await
+    }
+}
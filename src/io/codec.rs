@@ -0,0 +1,15 @@
+mod decoder;
+mod encoder;
+mod framed;
+mod framed_read;
+mod framed_write;
+mod length_delimited_codec;
+mod lines_codec;
+
+pub use decoder::*;
+pub use encoder::*;
+pub use framed::*;
+pub use framed_read::*;
+pub use framed_write::*;
+pub use length_delimited_codec::*;
+pub use lines_codec::*;
@@ -0,0 +1,18 @@
+use std::io;
+
+pub trait Decoder {
+    type Item;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Self::Item>>;
+
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Self::Item>> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if src.is_empty() => Ok(None),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Bytes remaining on stream",
+            )),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+use std::io;
+
+pub trait Encoder<Item> {
+    fn encode(&mut self, item: Item, dst: &mut Vec<u8>) -> io::Result<()>;
+}
@@ -0,0 +1,337 @@
+use std::{fmt, io};
+
+use super::{Decoder, Encoder};
+use crate::{
+    io::{AsyncRead, AsyncWrite, INIT_BUFFER_SIZE},
+    sink::Sink,
+    stream::Stream,
+};
+
+const BACKPRESSURE_BOUNDARY: usize = INIT_BUFFER_SIZE * 2;
+
+pub struct Framed<T, U> {
+    inner: T,
+    codec: U,
+    read_state: ReadState,
+    write_state: WriteState,
+}
+
+impl<T, U> Framed<T, U> {
+    pub fn new(inner: T, codec: U) -> Framed<T, U> {
+        Framed {
+            inner,
+            codec,
+            read_state: ReadState::new(),
+            write_state: WriteState::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn codec(&self) -> &U {
+        &self.codec
+    }
+
+    pub fn codec_mut(&mut self) -> &mut U {
+        &mut self.codec
+    }
+
+    pub fn read_buffer(&self) -> &[u8] {
+        &self.read_state.buffer
+    }
+
+    pub fn write_buffer(&self) -> &[u8] {
+        &self.write_state.buffer
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: AsyncRead, U: Decoder> Framed<T, U> {
+    pub async fn next(&mut self) -> Option<io::Result<U::Item>> {
+        self.read_state.next(&mut self.inner, &mut self.codec).await
+    }
+}
+
+impl<T: AsyncWrite, U> Framed<T, U> {
+    pub async fn feed<I>(&mut self, item: I) -> io::Result<()>
+    where
+        U: Encoder<I>,
+    {
+        self.write_state
+            .feed(&mut self.inner, &mut self.codec, item)
+            .await
+    }
+
+    pub async fn send<I>(&mut self, item: I) -> io::Result<()>
+    where
+        U: Encoder<I>,
+    {
+        self.feed(item).await?;
+        self.flush().await
+    }
+
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.write_state.flush(&mut self.inner).await
+    }
+
+    pub async fn close(&mut self) -> io::Result<()> {
+        self.write_state.close(&mut self.inner).await
+    }
+}
+
+impl<T: AsyncRead, U: Decoder> Stream for Framed<T, U> {
+    type Item = io::Result<U::Item>;
+
+    async fn next(&mut self) -> Option<io::Result<U::Item>> {
+        Framed::next(self).await
+    }
+}
+
+impl<I, T: AsyncWrite, U: Encoder<I>> Sink<I> for Framed<T, U> {
+    type Error = io::Error;
+
+    async fn ready(&mut self) -> io::Result<()> {
+        self.write_state.ready(&mut self.inner).await
+    }
+
+    async fn feed(&mut self, item: I) -> io::Result<()> {
+        Framed::feed(self, item).await
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        Framed::flush(self).await
+    }
+
+    async fn close(&mut self) -> io::Result<()> {
+        Framed::close(self).await
+    }
+}
+
+impl<T: fmt::Debug, U: fmt::Debug> fmt::Debug for Framed<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Framed")
+            .field("inner", &self.inner)
+            .field("codec", &self.codec)
+            .finish()
+    }
+}
+
+pub(super) struct ReadState {
+    pub(super) buffer: Vec<u8>,
+    eof: bool,
+}
+
+impl ReadState {
+    pub(super) fn new() -> ReadState {
+        ReadState {
+            buffer: Vec::with_capacity(INIT_BUFFER_SIZE),
+            eof: false,
+        }
+    }
+
+    pub(super) async fn next<R, D>(
+        &mut self,
+        reader: &mut R,
+        decoder: &mut D,
+    ) -> Option<io::Result<D::Item>>
+    where
+        R: AsyncRead,
+        D: Decoder,
+    {
+        let mut chunk = [0u8; INIT_BUFFER_SIZE];
+
+        loop {
+            if self.eof {
+                return match decoder.decode_eof(&mut self.buffer) {
+                    Ok(frame) => frame.map(Ok),
+                    Err(error) => {
+                        self.buffer.clear();
+
+                        Some(Err(error))
+                    }
+                };
+            }
+
+            match decoder.decode(&mut self.buffer) {
+                Ok(Some(frame)) => return Some(Ok(frame)),
+                Ok(None) => {}
+                Err(error) => return Some(Err(error)),
+            }
+
+            match reader.read(&mut chunk).await {
+                Ok(0) => self.eof = true,
+                Ok(length) => self.buffer.extend_from_slice(&chunk[..length]),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+pub(super) struct WriteState {
+    pub(super) buffer: Vec<u8>,
+}
+
+impl WriteState {
+    pub(super) fn new() -> WriteState {
+        WriteState {
+            buffer: Vec::with_capacity(INIT_BUFFER_SIZE),
+        }
+    }
+
+    pub(super) async fn ready<W: AsyncWrite>(&mut self, writer: &mut W) -> io::Result<()> {
+        if self.buffer.len() >= BACKPRESSURE_BOUNDARY {
+            self.write(writer).await?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) async fn feed<W, E, I>(
+        &mut self,
+        writer: &mut W,
+        encoder: &mut E,
+        item: I,
+    ) -> io::Result<()>
+    where
+        W: AsyncWrite,
+        E: Encoder<I>,
+    {
+        self.ready(writer).await?;
+        encoder.encode(item, &mut self.buffer)
+    }
+
+    pub(super) async fn flush<W: AsyncWrite>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.write(writer).await?;
+        writer.flush().await
+    }
+
+    pub(super) async fn close<W: AsyncWrite>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.write(writer).await?;
+        writer.shutdown().await
+    }
+
+    async fn write<W: AsyncWrite>(&mut self, writer: &mut W) -> io::Result<()> {
+        while !self.buffer.is_empty() {
+            match writer.write(&self.buffer).await {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(length) => {
+                    self.buffer.drain(..length);
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::{poll_fn, Future},
+        pin::pin,
+        task::Poll,
+    };
+
+    use super::*;
+    use crate::{
+        io::codec::{FramedRead, LengthDelimitedCodec, LinesCodec},
+        test_util::{block_on, tcp_pair, Mock},
+    };
+
+    #[test]
+    fn framed_round_trips_over_a_stream() {
+        block_on(async {
+            let (client, server) = tcp_pair().await;
+            let mut client = Framed::new(client, LinesCodec::new());
+            let mut server = Framed::new(server, LinesCodec::new());
+
+            client.send("ping").await.unwrap();
+            assert_eq!(server.next().await.unwrap().unwrap(), "ping");
+
+            server.feed("pong").await.unwrap();
+            server.feed("bye").await.unwrap();
+            server.close().await.unwrap();
+            assert_eq!(client.next().await.unwrap().unwrap(), "pong");
+            assert_eq!(client.next().await.unwrap().unwrap(), "bye");
+            assert!(client.next().await.is_none());
+        });
+    }
+
+    #[test]
+    fn dropped_next_leaves_the_read_buffer_intact() {
+        block_on(async {
+            let (mut client, server) = tcp_pair().await;
+            let mut framed = FramedRead::new(server, LengthDelimitedCodec::new());
+
+            client.write_all(&[0, 0, 0, 5, b'h']).await.unwrap();
+
+            while framed.read_buffer().is_empty() {
+                let mut next = pin!(framed.next());
+
+                poll_fn(|context| {
+                    assert!(next.as_mut().poll(context).is_pending());
+
+                    Poll::Ready(())
+                })
+                .await;
+            }
+
+            assert_eq!(framed.read_buffer(), b"h");
+
+            client.write_all(b"ello").await.unwrap();
+            assert_eq!(framed.next().await.unwrap().unwrap(), b"hello");
+        });
+    }
+
+    #[test]
+    fn truncated_frame_is_an_error_at_eof() {
+        block_on(async {
+            let mut framed = FramedRead::new(
+                Mock::new([&[0, 0, 0, 8, 1, 2][..]]),
+                LengthDelimitedCodec::new(),
+            );
+
+            assert_eq!(
+                framed.next().await.unwrap().unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+            assert!(framed.next().await.is_none());
+        });
+    }
+
+    #[test]
+    fn oversized_frame_is_reported_once_and_skipped() {
+        block_on(async {
+            let mut codec = LengthDelimitedCodec::new();
+
+            codec.set_max_frame_length(4);
+
+            let mut framed = FramedRead::new(
+                Mock::new([
+                    &[0, 0, 0, 9, 1, 2, 3][..],
+                    &[4, 5, 6, 7, 8, 9, 0, 0, 0, 2, b'o', b'k'],
+                ]),
+                codec,
+            );
+
+            assert_eq!(
+                framed.next().await.unwrap().unwrap_err().kind(),
+                io::ErrorKind::InvalidData
+            );
+            assert_eq!(framed.next().await.unwrap().unwrap(), b"ok");
+            assert!(framed.next().await.is_none());
+        });
+    }
+}
@@ -0,0 +1,67 @@
+use std::{fmt, io};
+
+use super::{framed::ReadState, Decoder};
+use crate::{io::AsyncRead, stream::Stream};
+
+pub struct FramedRead<R, D> {
+    reader: R,
+    decoder: D,
+    state: ReadState,
+}
+
+impl<R, D> FramedRead<R, D> {
+    pub fn new(reader: R, decoder: D) -> FramedRead<R, D> {
+        FramedRead {
+            reader,
+            decoder,
+            state: ReadState::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
+    pub fn decoder_mut(&mut self) -> &mut D {
+        &mut self.decoder
+    }
+
+    pub fn read_buffer(&self) -> &[u8] {
+        &self.state.buffer
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: AsyncRead, D: Decoder> FramedRead<R, D> {
+    pub async fn next(&mut self) -> Option<io::Result<D::Item>> {
+        self.state.next(&mut self.reader, &mut self.decoder).await
+    }
+}
+
+impl<R: AsyncRead, D: Decoder> Stream for FramedRead<R, D> {
+    type Item = io::Result<D::Item>;
+
+    async fn next(&mut self) -> Option<io::Result<D::Item>> {
+        FramedRead::next(self).await
+    }
+}
+
+impl<R: fmt::Debug, D: fmt::Debug> fmt::Debug for FramedRead<R, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FramedRead")
+            .field("reader", &self.reader)
+            .field("decoder", &self.decoder)
+            .finish()
+    }
+}
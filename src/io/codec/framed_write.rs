@@ -0,0 +1,100 @@
+use std::{fmt, io};
+
+use super::{framed::WriteState, Encoder};
+use crate::{io::AsyncWrite, sink::Sink};
+
+pub struct FramedWrite<W, E> {
+    writer: W,
+    encoder: E,
+    state: WriteState,
+}
+
+impl<W, E> FramedWrite<W, E> {
+    pub fn new(writer: W, encoder: E) -> FramedWrite<W, E> {
+        FramedWrite {
+            writer,
+            encoder,
+            state: WriteState::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    pub fn encoder_mut(&mut self) -> &mut E {
+        &mut self.encoder
+    }
+
+    pub fn write_buffer(&self) -> &[u8] {
+        &self.state.buffer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: AsyncWrite, E> FramedWrite<W, E> {
+    pub async fn feed<I>(&mut self, item: I) -> io::Result<()>
+    where
+        E: Encoder<I>,
+    {
+        self.state
+            .feed(&mut self.writer, &mut self.encoder, item)
+            .await
+    }
+
+    pub async fn send<I>(&mut self, item: I) -> io::Result<()>
+    where
+        E: Encoder<I>,
+    {
+        self.feed(item).await?;
+        self.flush().await
+    }
+
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.state.flush(&mut self.writer).await
+    }
+
+    pub async fn close(&mut self) -> io::Result<()> {
+        self.state.close(&mut self.writer).await
+    }
+}
+
+impl<I, W: AsyncWrite, E: Encoder<I>> Sink<I> for FramedWrite<W, E> {
+    type Error = io::Error;
+
+    async fn ready(&mut self) -> io::Result<()> {
+        self.state.ready(&mut self.writer).await
+    }
+
+    async fn feed(&mut self, item: I) -> io::Result<()> {
+        FramedWrite::feed(self, item).await
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        FramedWrite::flush(self).await
+    }
+
+    async fn close(&mut self) -> io::Result<()> {
+        FramedWrite::close(self).await
+    }
+}
+
+impl<W: fmt::Debug, E: fmt::Debug> fmt::Debug for FramedWrite<W, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FramedWrite")
+            .field("writer", &self.writer)
+            .field("encoder", &self.encoder)
+            .finish()
+    }
+}
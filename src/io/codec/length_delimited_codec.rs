@@ -0,0 +1,249 @@
+use std::io;
+
+use super::{Decoder, Encoder};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Endianness {
+    #[default]
+    Big,
+    Little,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum DecodeState {
+    Head,
+    Data(usize),
+    Discard(u64),
+}
+
+#[derive(Clone, Debug)]
+pub struct LengthDelimitedCodec {
+    length_field_length: usize,
+    endianness: Endianness,
+    max_frame_length: usize,
+    state: DecodeState,
+}
+
+impl LengthDelimitedCodec {
+    pub fn new() -> LengthDelimitedCodec {
+        LengthDelimitedCodec {
+            length_field_length: 4,
+            endianness: Endianness::Big,
+            max_frame_length: 8 * 1024 * 1024,
+            state: DecodeState::Head,
+        }
+    }
+
+    pub fn length_field_length(&self) -> usize {
+        self.length_field_length
+    }
+
+    pub fn set_length_field_length(&mut self, length_field_length: usize) {
+        if !(1..=8).contains(&length_field_length) {
+            panic!("Length field length should be between 1 and 8 bytes");
+        }
+
+        self.length_field_length = length_field_length;
+    }
+
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    pub fn max_frame_length(&self) -> usize {
+        self.max_frame_length
+    }
+
+    pub fn set_max_frame_length(&mut self, max_frame_length: usize) {
+        self.max_frame_length = max_frame_length;
+    }
+
+    fn frame_too_big() -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, "Frame length limit exceeded")
+    }
+}
+
+impl Default for LengthDelimitedCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for LengthDelimitedCodec {
+    type Item = Vec<u8>;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            match self.state {
+                DecodeState::Head => {
+                    if src.len() < self.length_field_length {
+                        return Ok(None);
+                    }
+
+                    let mut bytes = [0u8; 8];
+                    let header = &src[..self.length_field_length];
+                    let length = match self.endianness {
+                        Endianness::Big => {
+                            bytes[8 - self.length_field_length..].copy_from_slice(header);
+                            u64::from_be_bytes(bytes)
+                        }
+                        Endianness::Little => {
+                            bytes[..self.length_field_length].copy_from_slice(header);
+                            u64::from_le_bytes(bytes)
+                        }
+                    };
+
+                    src.drain(..self.length_field_length);
+
+                    if length > self.max_frame_length as u64 {
+                        self.state = DecodeState::Discard(length);
+
+                        return Err(Self::frame_too_big());
+                    }
+
+                    self.state = DecodeState::Data(length as usize);
+                }
+                DecodeState::Data(length) => {
+                    if src.len() < length {
+                        src.reserve(length - src.len());
+
+                        return Ok(None);
+                    }
+
+                    self.state = DecodeState::Head;
+
+                    return Ok(Some(src.drain(..length).collect()));
+                }
+                DecodeState::Discard(remaining) => {
+                    let length = remaining.min(src.len() as u64);
+
+                    src.drain(..length as usize);
+
+                    if length < remaining {
+                        self.state = DecodeState::Discard(remaining - length);
+
+                        return Ok(None);
+                    }
+
+                    self.state = DecodeState::Head;
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if src.is_empty() && !matches!(self.state, DecodeState::Data(_)) => {
+                self.state = DecodeState::Head;
+
+                Ok(None)
+            }
+            None => {
+                self.state = DecodeState::Head;
+
+                Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "Frame truncated by end of stream",
+                ))
+            }
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for LengthDelimitedCodec {
+    fn encode(&mut self, item: T, dst: &mut Vec<u8>) -> io::Result<()> {
+        let data = item.as_ref();
+
+        if data.len() > self.max_frame_length
+            || (self.length_field_length < 8
+                && data.len() as u64 >= 1 << (self.length_field_length * 8))
+        {
+            return Err(Self::frame_too_big());
+        }
+
+        dst.reserve(self.length_field_length + data.len());
+
+        match self.endianness {
+            Endianness::Big => dst.extend_from_slice(
+                &(data.len() as u64).to_be_bytes()[8 - self.length_field_length..],
+            ),
+            Endianness::Little => dst
+                .extend_from_slice(&(data.len() as u64).to_le_bytes()[..self.length_field_length]),
+        }
+
+        dst.extend_from_slice(data);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_frames() {
+        let mut codec = LengthDelimitedCodec::new();
+        let mut buffer = Vec::new();
+
+        codec.set_length_field_length(2);
+        codec.set_endianness(Endianness::Little);
+        codec.encode(b"hello", &mut buffer).unwrap();
+        codec.encode(b"", &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..2], &[5, 0]);
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), b"hello");
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), b"");
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_frames_over_the_limit() {
+        let mut codec = LengthDelimitedCodec::new();
+        let mut buffer = vec![0, 0, 1, 0];
+
+        codec.set_max_frame_length(255);
+
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(codec.encode([0; 256], &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn skips_frames_over_the_limit() {
+        let mut codec = LengthDelimitedCodec::new();
+        let mut buffer = vec![0, 0, 0, 6, b'o', b'v', b'e'];
+
+        codec.set_max_frame_length(4);
+
+        assert!(codec.decode(&mut buffer).is_err());
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        assert!(buffer.is_empty());
+
+        buffer.extend_from_slice(b"rsz\0\0\0\x02ok");
+
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), b"ok");
+        assert!(codec.decode_eof(&mut buffer).unwrap().is_none());
+    }
+
+    #[test]
+    fn decode_eof_reports_truncated_frames() {
+        let mut codec = LengthDelimitedCodec::new();
+        let mut buffer = vec![0, 0, 0, 4];
+
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        assert!(buffer.is_empty());
+        assert_eq!(
+            codec.decode_eof(&mut buffer).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert!(codec.decode_eof(&mut Vec::new()).unwrap().is_none());
+    }
+}
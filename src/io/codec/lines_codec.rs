@@ -0,0 +1,159 @@
+use std::{io, mem};
+
+use super::{Decoder, Encoder};
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct LinesCodec {
+    next_index: usize,
+    max_length: usize,
+    is_discarding: bool,
+}
+
+impl LinesCodec {
+    pub fn new() -> LinesCodec {
+        LinesCodec {
+            next_index: 0,
+            max_length: usize::MAX,
+            is_discarding: false,
+        }
+    }
+
+    pub fn new_with_max_length(max_length: usize) -> LinesCodec {
+        LinesCodec {
+            max_length,
+            ..LinesCodec::new()
+        }
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+impl Default for LinesCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn without_carriage_return(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+fn into_string(line: &[u8]) -> io::Result<String> {
+    String::from_utf8(without_carriage_return(line).to_vec())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+impl Decoder for LinesCodec {
+    type Item = String;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<String>> {
+        loop {
+            let read_to = src.len().min(self.max_length.saturating_add(1));
+            let newline_offset = src[self.next_index..read_to]
+                .iter()
+                .position(|byte| *byte == b'\n');
+
+            match (self.is_discarding, newline_offset) {
+                (true, Some(offset)) => {
+                    src.drain(..self.next_index + offset + 1);
+                    self.is_discarding = false;
+                    self.next_index = 0;
+                }
+                (true, None) => {
+                    src.drain(..read_to);
+                    self.next_index = 0;
+
+                    if src.is_empty() {
+                        return Ok(None);
+                    }
+                }
+                (false, Some(offset)) => {
+                    let newline_index = self.next_index + offset;
+                    let line: Vec<u8> = src.drain(..=newline_index).collect();
+
+                    self.next_index = 0;
+
+                    return into_string(&line[..newline_index]).map(Some);
+                }
+                (false, None) if src.len() > self.max_length => {
+                    self.is_discarding = true;
+
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Line length limit exceeded",
+                    ));
+                }
+                (false, None) => {
+                    self.next_index = read_to;
+
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> io::Result<Option<String>> {
+        match self.decode(src)? {
+            Some(line) => Ok(Some(line)),
+            None if src.is_empty() || self.is_discarding => {
+                src.clear();
+                self.next_index = 0;
+
+                Ok(None)
+            }
+            None => {
+                let line = mem::take(src);
+
+                self.next_index = 0;
+
+                into_string(&line).map(Some)
+            }
+        }
+    }
+}
+
+impl<T: AsRef<str>> Encoder<T> for LinesCodec {
+    fn encode(&mut self, item: T, dst: &mut Vec<u8>) -> io::Result<()> {
+        let line = item.as_ref();
+
+        dst.reserve(line.len() + 1);
+        dst.extend_from_slice(line.as_bytes());
+        dst.push(b'\n');
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_lines_and_the_unterminated_tail() {
+        let mut codec = LinesCodec::new();
+        let mut buffer = b"first\r\nsecond\nthi".to_vec();
+
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), "first");
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), "second");
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+
+        buffer.extend_from_slice(b"rd");
+
+        assert_eq!(codec.decode_eof(&mut buffer).unwrap().unwrap(), "third");
+        assert!(codec.decode_eof(&mut buffer).unwrap().is_none());
+    }
+
+    #[test]
+    fn discards_lines_over_the_limit() {
+        let mut codec = LinesCodec::new_with_max_length(4);
+        let mut buffer = b"too long\nok\n".to_vec();
+
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), "ok");
+    }
+}
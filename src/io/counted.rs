@@ -0,0 +1,172 @@
+use std::{
+    fmt, io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use super::{AsyncRead, AsyncWrite};
+
+#[derive(Debug, Default)]
+struct CounterState {
+    read: AtomicU64,
+    written: AtomicU64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Counter(Arc<CounterState>);
+
+impl Counter {
+    pub fn new() -> Counter {
+        Counter::default()
+    }
+
+    pub fn bytes_read(&self) -> u64 {
+        self.0.read.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_written(&self) -> u64 {
+        self.0.written.load(Ordering::Relaxed)
+    }
+}
+
+pub struct Counted<T> {
+    inner: T,
+    counter: Counter,
+}
+
+impl<T> Counted<T> {
+    pub fn new(inner: T) -> Counted<T> {
+        Self::with_counter(inner, Counter::new())
+    }
+
+    pub fn with_counter(inner: T, counter: Counter) -> Counted<T> {
+        Counted { inner, counter }
+    }
+
+    pub fn counter(&self) -> &Counter {
+        &self.counter
+    }
+
+    pub fn bytes_read(&self) -> u64 {
+        self.counter.bytes_read()
+    }
+
+    pub fn bytes_written(&self) -> u64 {
+        self.counter.bytes_written()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Counted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Counted")
+            .field("inner", &self.inner)
+            .field("bytes_read", &self.bytes_read())
+            .field("bytes_written", &self.bytes_written())
+            .finish()
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for Counted<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.inner.read(buf).await?;
+
+        self.counter
+            .0
+            .read
+            .fetch_add(length as u64, Ordering::Relaxed);
+
+        Ok(length)
+    }
+
+    async fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        let length = self.inner.read_vectored(bufs).await?;
+
+        self.counter
+            .0
+            .read
+            .fetch_add(length as u64, Ordering::Relaxed);
+
+        Ok(length)
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for Counted<T> {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let length = self.inner.write(buf).await?;
+
+        self.counter
+            .0
+            .written
+            .fetch_add(length as u64, Ordering::Relaxed);
+
+        Ok(length)
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+
+    async fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        let length = self.inner.write_vectored(bufs).await?;
+
+        self.counter
+            .0
+            .written
+            .fetch_add(length as u64, Ordering::Relaxed);
+
+        Ok(length)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{block_on, Mock};
+
+    #[test]
+    fn counted_tracks_both_directions_through_a_shared_counter() {
+        block_on(async {
+            let counter = Counter::new();
+            let mut first = Counted::with_counter(Mock::new([&b"abc"[..]]), counter.clone());
+            let mut second = Counted::with_counter(Mock::default(), counter.clone());
+            let mut received = Vec::new();
+
+            first.read_to_end(&mut received).await.unwrap();
+            second.write_all(b"hello").await.unwrap();
+            let vectored = second
+                .write_vectored(&[io::IoSlice::new(b"ab"), io::IoSlice::new(b"cd")])
+                .await
+                .unwrap();
+
+            assert_eq!(
+                (first.bytes_read(), first.bytes_written()),
+                (3, 5 + vectored as u64)
+            );
+            assert_eq!(counter.bytes_read(), 3);
+            assert_eq!(counter.bytes_written(), 5 + vectored as u64);
+            assert_eq!(second.get_ref().output, &b"helloabcd"[..5 + vectored]);
+        });
+    }
+}
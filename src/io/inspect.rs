@@ -0,0 +1,89 @@
+use std::{fmt, io};
+
+use super::{AsyncRead, AsyncWrite};
+
+pub struct Inspect<T, F> {
+    inner: T,
+    f: F,
+}
+
+impl<T, F: FnMut(&[u8])> Inspect<T, F> {
+    pub fn new(inner: T, f: F) -> Self {
+        Self { inner, f }
+    }
+}
+
+impl<T, F> Inspect<T, F> {
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Inspect<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inspect")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T: AsyncRead, F: FnMut(&[u8])> AsyncRead for Inspect<T, F> {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.inner.read(buf).await?;
+
+        (self.f)(&buf[..length]);
+
+        Ok(length)
+    }
+}
+
+impl<T: AsyncWrite, F: FnMut(&[u8])> AsyncWrite for Inspect<T, F> {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let length = self.inner.write(buf).await?;
+
+        (self.f)(&buf[..length]);
+
+        Ok(length)
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{block_on, Mock};
+
+    #[test]
+    fn inspect_sees_both_directions() {
+        block_on(async {
+            let mut chunks = Vec::new();
+            let mut inspect = Inspect::new(Mock::new([&b"ping"[..]]), |buf: &[u8]| {
+                chunks.push(buf.to_vec())
+            });
+            let mut buffer = [0; 8];
+
+            let length = inspect.read(&mut buffer).await.unwrap();
+            inspect.write_all(&buffer[..length]).await.unwrap();
+            inspect.write_all(b"pong").await.unwrap();
+
+            assert_eq!(inspect.get_ref().output, b"pingpong");
+            drop(inspect);
+            assert_eq!(chunks, [&b"ping"[..], b"ping", b"pong"]);
+        });
+    }
+}
@@ -0,0 +1,46 @@
+use std::ops::{BitOr, BitOrAssign};
+
+use crate::runtime::driver::{PRIORITY, READABLE, WRITABLE};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interest(u32);
+
+impl Interest {
+    pub const READABLE: Interest = Interest(READABLE);
+    pub const WRITABLE: Interest = Interest(WRITABLE);
+    pub const PRIORITY: Interest = Interest(PRIORITY);
+
+    pub const fn add(self, other: Interest) -> Interest {
+        Interest(self.0 | other.0)
+    }
+
+    pub const fn is_readable(self) -> bool {
+        self.0 & READABLE != 0
+    }
+
+    pub const fn is_writable(self) -> bool {
+        self.0 & WRITABLE != 0
+    }
+
+    pub const fn is_priority(self) -> bool {
+        self.0 & PRIORITY != 0
+    }
+
+    pub(crate) const fn events(self) -> u32 {
+        self.0
+    }
+}
+
+impl BitOr for Interest {
+    type Output = Interest;
+
+    fn bitor(self, rhs: Interest) -> Interest {
+        self.add(rhs)
+    }
+}
+
+impl BitOrAssign for Interest {
+    fn bitor_assign(&mut self, rhs: Interest) {
+        *self = self.add(rhs)
+    }
+}
@@ -0,0 +1,69 @@
+use std::{fmt, io, mem};
+
+use super::AsyncBufRead;
+use crate::stream::Stream;
+
+pub struct Lines<B> {
+    reader: B,
+    buffer: Vec<u8>,
+}
+
+impl<B> Lines<B> {
+    pub(super) fn new(reader: B) -> Lines<B> {
+        Lines {
+            reader,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> B {
+        self.reader
+    }
+
+    pub fn get_ref(&self) -> &B {
+        &self.reader
+    }
+
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.reader
+    }
+}
+
+impl<B: AsyncBufRead> Lines<B> {
+    pub async fn next_line(&mut self) -> io::Result<Option<String>> {
+        self.reader.read_until(b'\n', &mut self.buffer).await?;
+
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+
+        let mut line = String::from_utf8(mem::take(&mut self.buffer))
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        if line.ends_with('\n') {
+            line.pop();
+
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+
+        Ok(Some(line))
+    }
+}
+
+impl<B: fmt::Debug> fmt::Debug for Lines<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lines")
+            .field("reader", &self.reader)
+            .finish()
+    }
+}
+
+impl<B: AsyncBufRead> Stream for Lines<B> {
+    type Item = io::Result<String>;
+
+    async fn next(&mut self) -> Option<io::Result<String>> {
+        self.next_line().await.transpose()
+    }
+}
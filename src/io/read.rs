@@ -1,6 +1,6 @@
 use std::{future::Future, io};
 
-use super::{Chain, Take, INIT_BUFFER_SIZE};
+use super::{AsyncWrite, Chain, Inspect, Take, Tee, INIT_BUFFER_SIZE};
 
 pub trait AsyncRead {
     fn read(&mut self, buf: &mut [u8]) -> impl Future<Output = io::Result<usize>>;
@@ -8,21 +8,12 @@ pub trait AsyncRead {
     fn read_vectored(
         &mut self,
         bufs: &mut [io::IoSliceMut<'_>],
-    ) -> impl Future<Output = io::Result<()>> {
+    ) -> impl Future<Output = io::Result<usize>> {
         async {
-            let mut buffer = Vec::with_capacity(
-                bufs.iter()
-                    .fold(0, |accumulator, buf| accumulator + buf.len()),
-            );
-
-            self.read_exact(&mut buffer).await?;
-
-            bufs.iter_mut().fold(0, |accumulator, buf| {
-                buf.clone_from_slice(&buffer[accumulator..]);
-                accumulator + buf.len()
-            });
-
-            Ok(())
+            match bufs.iter_mut().find(|buf| !buf.is_empty()) {
+                Some(buf) => self.read(buf).await,
+                None => Ok(0),
+            }
         }
     }
 
@@ -70,6 +61,10 @@ pub trait AsyncRead {
             let mut total = 0;
 
             loop {
+                if total == buf.len() {
+                    break Ok(());
+                }
+
                 match self.read(&mut buf[total..]).await {
                     Ok(0) => {
                         break if total != buf.len() {
@@ -116,4 +111,66 @@ pub trait AsyncRead {
     {
         async move { Take::new(self, limit) }
     }
+
+    fn tee<W: AsyncWrite>(self, writer: W) -> impl Future<Output = Tee<Self, W>>
+    where
+        Self: Sized,
+    {
+        async { Tee::new(self, writer) }
+    }
+
+    fn inspect<F: FnMut(&[u8])>(self, f: F) -> impl Future<Output = Inspect<Self, F>>
+    where
+        Self: Sized,
+    {
+        async { Inspect::new(self, f) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::pending;
+
+    use super::*;
+    use crate::test_util::{block_on, Mock};
+
+    struct NoEmptyReads(Mock);
+
+    impl AsyncRead for NoEmptyReads {
+        async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            assert!(!buf.is_empty(), "read with an empty buffer");
+
+            if self.0.input.is_empty() {
+                pending().await
+            } else {
+                self.0.read(buf).await
+            }
+        }
+    }
+
+    #[test]
+    fn read_exact_stops_once_the_buffer_is_full() {
+        block_on(async {
+            let mut reader = NoEmptyReads(Mock::new([&b"ab"[..], b"cd"]));
+            let mut buffer = [0; 4];
+
+            reader.read_exact(&mut buffer).await.unwrap();
+            reader.read_exact(&mut []).await.unwrap();
+
+            assert_eq!(&buffer, b"abcd");
+        });
+    }
+
+    #[test]
+    fn read_exact_reports_early_eof() {
+        block_on(async {
+            let mut buffer = [0; 4];
+            let error = Mock::new([&b"ab"[..]])
+                .read_exact(&mut buffer)
+                .await
+                .unwrap_err();
+
+            assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        });
+    }
 }
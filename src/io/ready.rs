@@ -0,0 +1,87 @@
+use std::ops::{BitAnd, BitOr, BitOrAssign, Sub};
+
+const READABLE: u32 = libc::EPOLLIN as u32;
+const WRITABLE: u32 = libc::EPOLLOUT as u32;
+const READ_CLOSED: u32 = libc::EPOLLRDHUP as u32;
+const WRITE_CLOSED: u32 = libc::EPOLLHUP as u32;
+const PRIORITY: u32 = libc::EPOLLPRI as u32;
+const ERROR: u32 = libc::EPOLLERR as u32;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Ready(u32);
+
+impl Ready {
+    pub const EMPTY: Ready = Ready(0);
+    pub const READABLE: Ready = Ready(READABLE);
+    pub const WRITABLE: Ready = Ready(WRITABLE);
+    pub const READ_CLOSED: Ready = Ready(READ_CLOSED);
+    pub const WRITE_CLOSED: Ready = Ready(WRITE_CLOSED);
+    pub const PRIORITY: Ready = Ready(PRIORITY);
+    pub const ERROR: Ready = Ready(ERROR);
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn is_readable(self) -> bool {
+        self.0 & (READABLE | READ_CLOSED | WRITE_CLOSED) != 0
+    }
+
+    pub const fn is_writable(self) -> bool {
+        self.0 & (WRITABLE | WRITE_CLOSED | ERROR) != 0
+    }
+
+    pub const fn is_read_closed(self) -> bool {
+        self.0 & (READ_CLOSED | WRITE_CLOSED) != 0
+    }
+
+    pub const fn is_write_closed(self) -> bool {
+        self.0 & WRITE_CLOSED != 0
+    }
+
+    pub const fn is_priority(self) -> bool {
+        self.0 & PRIORITY != 0
+    }
+
+    pub const fn is_error(self) -> bool {
+        self.0 & ERROR != 0
+    }
+
+    pub(crate) const fn from_events(events: u32) -> Ready {
+        Ready(events & (READABLE | WRITABLE | READ_CLOSED | WRITE_CLOSED | PRIORITY | ERROR))
+    }
+
+    pub(crate) const fn events(self) -> u32 {
+        self.0
+    }
+}
+
+impl BitOr for Ready {
+    type Output = Ready;
+
+    fn bitor(self, rhs: Ready) -> Ready {
+        Ready(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Ready {
+    fn bitor_assign(&mut self, rhs: Ready) {
+        self.0 |= rhs.0
+    }
+}
+
+impl BitAnd for Ready {
+    type Output = Ready;
+
+    fn bitand(self, rhs: Ready) -> Ready {
+        Ready(self.0 & rhs.0)
+    }
+}
+
+impl Sub for Ready {
+    type Output = Ready;
+
+    fn sub(self, rhs: Ready) -> Ready {
+        Ready(self.0 & !rhs.0)
+    }
+}
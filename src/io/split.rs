@@ -0,0 +1,157 @@
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    io,
+    pin::pin,
+    sync::Arc,
+};
+
+use super::{AsyncRead, AsyncWrite};
+use crate::{
+    runtime::pending,
+    sync::{Mutex, TryLock},
+};
+
+pub struct ReadHalf<T> {
+    inner: Arc<Mutex<T>>,
+}
+
+pub struct WriteHalf<T> {
+    inner: Arc<Mutex<T>>,
+}
+
+pub fn split<T: AsyncRead + AsyncWrite>(stream: T) -> (ReadHalf<T>, WriteHalf<T>) {
+    let inner = Arc::new(Mutex::new(stream));
+
+    (
+        ReadHalf {
+            inner: inner.clone(),
+        },
+        WriteHalf { inner },
+    )
+}
+
+impl<T> ReadHalf<T> {
+    pub fn is_pair_of(&self, other: &WriteHalf<T>) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    pub fn unsplit(self, write_half: WriteHalf<T>) -> T {
+        if !self.is_pair_of(&write_half) {
+            panic!("Unrelated WriteHalf passed to ReadHalf::unsplit");
+        }
+
+        drop(write_half);
+
+        Arc::into_inner(self.inner)
+            .expect("WriteHalf still referenced after being dropped")
+            .into_inner()
+    }
+}
+
+impl<T> fmt::Debug for ReadHalf<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadHalf").finish()
+    }
+}
+
+impl<T> fmt::Debug for WriteHalf<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteHalf").finish()
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for ReadHalf<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // A read can stay pending indefinitely, so the lock is only held while
+        // polling and the write half can get in between. A pending read has
+        // not consumed anything, so starting it over on the next poll is fine.
+        poll_fn(|context| {
+            let TryLock::Guard(mut inner) = self.inner.try_lock() else {
+                return pending();
+            };
+
+            let poll = pin!(inner.read(&mut *buf)).poll(context);
+
+            poll
+        })
+        .await
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for WriteHalf<T> {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.lock().await.write(buf).await
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.inner.lock().await.flush().await
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.lock().await.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        io::BufWriter,
+        test_util::{block_on, tcp_pair, Mock},
+        thread::{sleep, spawn},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn write_half_keeps_buffered_writes_across_pending() {
+        block_on(async {
+            let mock = Mock {
+                write_limit: Some(3),
+                stall_writes: true,
+                ..Mock::default()
+            };
+            let mut writer = WriteHalf {
+                inner: Arc::new(Mutex::new(BufWriter::with_capacity(64, mock))),
+            };
+
+            writer.write_all(b"hello ").await.unwrap();
+            writer.write_all(b"world").await.unwrap();
+            writer.flush().await.unwrap();
+            writer.write_all(b"!").await.unwrap();
+            writer.shutdown().await.unwrap();
+
+            let mock = Arc::into_inner(writer.inner)
+                .unwrap()
+                .into_inner()
+                .into_inner();
+
+            assert_eq!(mock.output, b"hello world!");
+            assert!(mock.shutdown);
+        });
+    }
+
+    #[test]
+    fn write_half_is_not_blocked_by_pending_read() {
+        block_on(async {
+            let (client, mut server) = tcp_pair().await;
+            let (mut reader, mut writer) = split(client);
+            let read = spawn(async move {
+                let mut buffer = [0; 4];
+
+                reader.read_exact(&mut buffer).await.unwrap();
+
+                buffer
+            });
+            let mut buffer = [0; 4];
+
+            sleep(Duration::from_millis(20)).await;
+            writer.write_all(b"ping").await.unwrap();
+            server.read_exact(&mut buffer).await.unwrap();
+            assert_eq!(&buffer, b"ping");
+
+            server.write_all(b"pong").await.unwrap();
+            assert_eq!(&read.await, b"pong");
+        });
+    }
+}
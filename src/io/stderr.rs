@@ -0,0 +1,26 @@
+use std::io;
+
+use super::{stdio::Stdio, AsyncWrite};
+
+#[derive(Debug)]
+pub struct Stderr(Stdio);
+
+impl Stderr {
+    pub(super) fn new() -> Stderr {
+        Stderr(Stdio::new(libc::STDERR_FILENO))
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.0.is_blocking()
+    }
+}
+
+impl AsyncWrite for Stderr {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf).await
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.0.flush().await
+    }
+}
@@ -0,0 +1,26 @@
+use std::io;
+
+use super::{stdio::Stdio, AsyncBufRead, AsyncRead, BufReader, Lines};
+
+#[derive(Debug)]
+pub struct Stdin(Stdio);
+
+impl Stdin {
+    pub(super) fn new() -> Stdin {
+        Stdin(Stdio::new(libc::STDIN_FILENO))
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.0.is_blocking()
+    }
+
+    pub fn lines(self) -> Lines<BufReader<Stdin>> {
+        BufReader::new(self).lines()
+    }
+}
+
+impl AsyncRead for Stdin {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf).await
+    }
+}
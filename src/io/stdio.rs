@@ -0,0 +1,313 @@
+use std::{
+    fmt,
+    future::poll_fn,
+    io, mem,
+    os::fd::RawFd,
+    sync::{
+        mpsc::{self, TryRecvError},
+        Mutex, OnceLock,
+    },
+    task::Poll,
+    thread,
+};
+
+use crate::{
+    runtime::{
+        driver::{driver, Driver, READABLE, WRITABLE},
+        pending,
+    },
+    sys::cvt,
+};
+
+const MAX_BLOCKING_BUFFER_SIZE: usize = 64 * 1024;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct Pending<T>(Mutex<mpsc::Receiver<T>>);
+
+impl<T: Send + 'static> Pending<T> {
+    fn spawn(f: impl FnOnce() -> T + Send + 'static) -> Pending<T> {
+        static STDIO_THREAD: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
+
+        let jobs = STDIO_THREAD.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel::<Job>();
+
+            thread::Builder::new()
+                .name("stdio".to_string())
+                .spawn(move || receiver.into_iter().for_each(|job| job()))
+                .expect("Can't spawn stdio thread");
+
+            sender
+        });
+        let (sender, receiver) = mpsc::channel();
+
+        jobs.send(Box::new(move || {
+            sender.send(f()).ok();
+        }))
+        .expect("Stdio thread exited");
+
+        Pending(Mutex::new(receiver))
+    }
+
+    async fn wait(&self) -> T {
+        poll_fn(|_context| {
+            let receiver = self.0.lock().expect("Stdio job is poisoned");
+
+            match receiver.try_recv() {
+                Ok(result) => Poll::Ready(result),
+                Err(TryRecvError::Empty) => pending(),
+                Err(TryRecvError::Disconnected) => panic!("Stdio job panicked"),
+            }
+        })
+        .await
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Socket,
+    Pollable,
+    Blocking,
+}
+
+pub(super) struct Stdio {
+    fd: RawFd,
+    mode: Mode,
+    buffer: Vec<u8>,
+    pending_read: Option<Pending<io::Result<Vec<u8>>>>,
+    pending_write: Option<Pending<io::Result<usize>>>,
+}
+
+impl Stdio {
+    pub(super) fn new(fd: RawFd) -> Stdio {
+        let mut stat: libc::stat = unsafe { mem::zeroed() };
+        let mode = if unsafe { libc::fstat(fd, &mut stat) } == -1 {
+            Mode::Blocking
+        } else {
+            match stat.st_mode & libc::S_IFMT {
+                libc::S_IFSOCK => Mode::Socket,
+                libc::S_IFIFO | libc::S_IFCHR => Mode::Pollable,
+                _ => Mode::Blocking,
+            }
+        };
+
+        Stdio {
+            fd,
+            mode,
+            buffer: Vec::new(),
+            pending_read: None,
+            pending_write: None,
+        }
+    }
+
+    pub(super) fn is_blocking(&self) -> bool {
+        self.mode == Mode::Blocking
+    }
+
+    pub(super) async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.buffer.is_empty() {
+            match self.mode {
+                Mode::Socket => return self.poll_io(READABLE, |fd| recv(fd, buf)).await,
+                Mode::Pollable => return self.poll_io(READABLE, |fd| read(fd, buf)).await,
+                Mode::Blocking => {
+                    let fd = self.fd;
+                    let length = buf.len().min(MAX_BLOCKING_BUFFER_SIZE);
+                    let pending_read = self.pending_read.get_or_insert_with(|| {
+                        Pending::spawn(move || {
+                            let mut buffer = vec![0; length];
+                            let length = read(fd, &mut buffer)?;
+
+                            buffer.truncate(length);
+
+                            Ok(buffer)
+                        })
+                    });
+                    let result = pending_read.wait().await;
+
+                    self.pending_read = None;
+                    self.buffer = result?;
+                }
+            }
+        }
+
+        let length = self.buffer.len().min(buf.len());
+
+        buf[..length].copy_from_slice(&self.buffer[..length]);
+        self.buffer.drain(..length);
+
+        Ok(length)
+    }
+
+    pub(super) async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.mode {
+            Mode::Socket => self.poll_io(WRITABLE, |fd| send(fd, buf)).await,
+            Mode::Pollable => {
+                let buf = &buf[..buf.len().min(libc::PIPE_BUF)];
+
+                self.poll_io(WRITABLE, |fd| write(fd, buf)).await
+            }
+            Mode::Blocking => {
+                self.flush().await?;
+
+                let fd = self.fd;
+                let buffer = buf[..buf.len().min(MAX_BLOCKING_BUFFER_SIZE)].to_vec();
+                let pending_write = self
+                    .pending_write
+                    .insert(Pending::spawn(move || write(fd, &buffer)));
+                let result = pending_write.wait().await;
+
+                self.pending_write = None;
+
+                result
+            }
+        }
+    }
+
+    pub(super) async fn flush(&mut self) -> io::Result<()> {
+        if let Some(pending_write) = &mut self.pending_write {
+            let result = pending_write.wait().await;
+
+            self.pending_write = None;
+            result?;
+        }
+
+        Ok(())
+    }
+
+    async fn poll_io(
+        &self,
+        events: u32,
+        mut f: impl FnMut(RawFd) -> io::Result<usize>,
+    ) -> io::Result<usize> {
+        loop {
+            driver().clear_ready(self.fd, events);
+            driver().ready(self.fd, events).await?;
+
+            match f(self.fd) {
+                Err(error)
+                    if matches!(
+                        error.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted
+                    ) => {}
+                result => return result,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Stdio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stdio")
+            .field("fd", &self.fd)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+fn read(fd: RawFd, buf: &mut [u8]) -> io::Result<usize> {
+    cvt(unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) })
+        .map(|length| length as usize)
+}
+
+fn write(fd: RawFd, buf: &[u8]) -> io::Result<usize> {
+    cvt(unsafe { libc::write(fd, buf.as_ptr() as *const libc::c_void, buf.len()) })
+        .map(|length| length as usize)
+}
+
+fn recv(fd: RawFd, buf: &mut [u8]) -> io::Result<usize> {
+    cvt(unsafe {
+        libc::recv(
+            fd,
+            buf.as_mut_ptr() as *mut libc::c_void,
+            buf.len(),
+            libc::MSG_DONTWAIT,
+        )
+    })
+    .map(|length| length as usize)
+}
+
+fn send(fd: RawFd, buf: &[u8]) -> io::Result<usize> {
+    cvt(unsafe {
+        libc::send(
+            fd,
+            buf.as_ptr() as *const libc::c_void,
+            buf.len(),
+            libc::MSG_DONTWAIT | libc::MSG_NOSIGNAL,
+        )
+    })
+    .map(|length| length as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        os::fd::{AsRawFd, FromRawFd, OwnedFd},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::test_util::block_on;
+
+    fn blocking_pipe() -> (OwnedFd, OwnedFd) {
+        let mut fds = [0; 2];
+
+        cvt(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) }).unwrap();
+
+        unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) }
+    }
+
+    #[test]
+    fn pipe_waits_for_the_driver() {
+        block_on(async {
+            let (reader, writer) = blocking_pipe();
+            let mut stdin = Stdio::new(reader.as_raw_fd());
+            let mut stdout = Stdio::new(writer.as_raw_fd());
+            let mut buffer = [0; 8];
+
+            assert_eq!(stdin.mode, Mode::Pollable);
+
+            let delayed = thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                write(writer.as_raw_fd(), b"later").unwrap();
+                writer
+            });
+
+            assert_eq!(stdin.read(&mut buffer).await.unwrap(), 5);
+            assert_eq!(&buffer[..5], b"later");
+
+            let writer = delayed.join().unwrap();
+
+            assert_eq!(stdout.write(b"now").await.unwrap(), 3);
+            assert_eq!(stdin.read(&mut buffer).await.unwrap(), 3);
+            assert_eq!(&buffer[..3], b"now");
+            drop(writer);
+        });
+    }
+
+    #[test]
+    fn regular_files_share_one_stdio_thread() {
+        block_on(async {
+            let path = std::env::temp_dir().join(format!("racing-stdio-{}", std::process::id()));
+            let file = File::create(&path).unwrap();
+            let mut stdout = Stdio::new(file.as_raw_fd());
+            let first = Pending::spawn(|| thread::current().id()).wait().await;
+            let second = Pending::spawn(|| thread::current().id()).wait().await;
+
+            assert!(stdout.is_blocking());
+            assert_eq!(first, second);
+            assert_ne!(first, thread::current().id());
+
+            assert_eq!(stdout.write(b"hello").await.unwrap(), 5);
+            stdout.flush().await.unwrap();
+
+            let file = File::open(&path).unwrap();
+            let mut stdin = Stdio::new(file.as_raw_fd());
+            let mut buffer = [0; 8];
+
+            assert_eq!(stdin.read(&mut buffer).await.unwrap(), 5);
+            assert_eq!(&buffer[..5], b"hello");
+            std::fs::remove_file(path).unwrap();
+        });
+    }
+}
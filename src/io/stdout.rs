@@ -0,0 +1,26 @@
+use std::io;
+
+use super::{stdio::Stdio, AsyncWrite};
+
+#[derive(Debug)]
+pub struct Stdout(Stdio);
+
+impl Stdout {
+    pub(super) fn new() -> Stdout {
+        Stdout(Stdio::new(libc::STDOUT_FILENO))
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.0.is_blocking()
+    }
+}
+
+impl AsyncWrite for Stdout {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf).await
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.0.flush().await
+    }
+}
@@ -0,0 +1,68 @@
+use std::{fmt, io};
+
+use super::{AsyncRead, AsyncWrite};
+
+pub struct Tee<R, W> {
+    pub(super) reader: R,
+    pub(super) writer: W,
+}
+
+impl<R, W> Tee<R, W> {
+    pub(super) fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+
+    pub fn get_ref(&self) -> (&R, &W) {
+        (&self.reader, &self.writer)
+    }
+
+    pub fn get_mut(&mut self) -> (&mut R, &mut W) {
+        (&mut self.reader, &mut self.writer)
+    }
+}
+
+impl<R: fmt::Debug, W: fmt::Debug> fmt::Debug for Tee<R, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tee")
+            .field("reader", &self.reader)
+            .field("writer", &self.writer)
+            .finish()
+    }
+}
+
+impl<R: AsyncRead, W: AsyncWrite> AsyncRead for Tee<R, W> {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.reader.read(buf).await?;
+
+        self.writer.write_all(&buf[..length]).await?;
+
+        Ok(length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{block_on, Mock};
+
+    #[test]
+    fn tee_mirrors_bytes_read() {
+        block_on(async {
+            let mut tee = Mock::new([&b"hello "[..], b"world"])
+                .tee(Mock::default())
+                .await;
+            let mut received = Vec::new();
+
+            tee.read_to_end(&mut received).await.unwrap();
+
+            let (_, mirrored) = tee.into_inner();
+
+            assert_eq!(received, b"hello world");
+            assert_eq!(mirrored.output, b"hello world");
+        });
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    fmt, io,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::{AsyncRead, AsyncWrite};
+use crate::thread::sleep;
+
+#[derive(Debug)]
+struct TokenBucketState {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+#[derive(Clone)]
+pub struct TokenBucket {
+    rate: u64,
+    burst: u64,
+    state: Arc<Mutex<TokenBucketState>>,
+}
+
+impl TokenBucket {
+    pub fn new(rate: u64, burst: u64) -> TokenBucket {
+        if rate == 0 || burst == 0 {
+            panic!("Token bucket rate and burst can't be zero");
+        }
+
+        TokenBucket {
+            rate,
+            burst,
+            state: Arc::new(Mutex::new(TokenBucketState {
+                tokens: burst as f64,
+                updated_at: Instant::now(),
+            })),
+        }
+    }
+
+    pub fn rate(&self) -> u64 {
+        self.rate
+    }
+
+    pub fn burst(&self) -> u64 {
+        self.burst
+    }
+
+    pub fn available(&self) -> u64 {
+        self.refill(&mut self.state.lock().expect("Token bucket is poisoned")) as u64
+    }
+
+    fn refill(&self, state: &mut TokenBucketState) -> f64 {
+        let now = Instant::now();
+
+        state.tokens = (state.tokens
+            + now.duration_since(state.updated_at).as_secs_f64() * self.rate as f64)
+            .min(self.burst as f64);
+        state.updated_at = now;
+
+        state.tokens
+    }
+
+    async fn available_for(&self, wanted: usize) -> usize {
+        loop {
+            let missing = {
+                let mut state = self.state.lock().expect("Token bucket is poisoned");
+                let tokens = self.refill(&mut state);
+
+                if tokens >= 1.0 {
+                    return (wanted as f64).min(tokens.floor()) as usize;
+                }
+
+                1.0 - tokens
+            };
+
+            sleep(Duration::from_secs_f64(missing / self.rate as f64)).await;
+        }
+    }
+
+    fn charge(&self, tokens: usize) {
+        if tokens > 0 {
+            let mut state = self.state.lock().expect("Token bucket is poisoned");
+
+            self.refill(&mut state);
+            state.tokens -= tokens as f64;
+        }
+    }
+}
+
+impl fmt::Debug for TokenBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenBucket")
+            .field("rate", &self.rate)
+            .field("burst", &self.burst)
+            .finish()
+    }
+}
+
+pub struct Throttle<T> {
+    inner: T,
+    bucket: TokenBucket,
+}
+
+impl<T> Throttle<T> {
+    pub fn new(inner: T, rate: u64, burst: u64) -> Throttle<T> {
+        Self::with_bucket(inner, TokenBucket::new(rate, burst))
+    }
+
+    pub fn with_bucket(inner: T, bucket: TokenBucket) -> Throttle<T> {
+        Throttle { inner, bucket }
+    }
+
+    pub fn bucket(&self) -> &TokenBucket {
+        &self.bucket
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Throttle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Throttle")
+            .field("inner", &self.inner)
+            .field("bucket", &self.bucket)
+            .finish()
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for Throttle<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return self.inner.read(buf).await;
+        }
+
+        let available = self.bucket.available_for(buf.len()).await;
+        let length = self.inner.read(&mut buf[..available]).await?;
+
+        self.bucket.charge(length);
+
+        Ok(length)
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for Throttle<T> {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return self.inner.write(buf).await;
+        }
+
+        let available = self.bucket.available_for(buf.len()).await;
+        let length = self.inner.write(&buf[..available]).await?;
+
+        self.bucket.charge(length);
+
+        Ok(length)
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::{
+        test_util::{block_on, tcp_pair, Mock},
+        thread::spawn,
+    };
+
+    #[test]
+    fn idle_reader_does_not_hold_shared_tokens() {
+        block_on(async {
+            let bucket = TokenBucket::new(100, 1000);
+            let (_peer, idle) = tcp_pair().await;
+            let mut idle = Throttle::with_bucket(idle, bucket.clone());
+            let _idle_read = spawn(async move {
+                let mut buffer = [0; 1000];
+
+                idle.read(&mut buffer).await
+            });
+            let mut active = Throttle::with_bucket(Mock::new([&[1; 500][..]]), bucket.clone());
+            let mut received = Vec::new();
+            let started = Instant::now();
+
+            sleep(Duration::from_millis(20)).await;
+            active.read_to_end(&mut received).await.unwrap();
+
+            assert_eq!(received.len(), 500);
+            assert!(started.elapsed() < Duration::from_secs(1));
+            assert!(bucket.available() <= 500);
+        });
+    }
+
+    #[test]
+    fn writer_is_held_to_the_rate_once_the_burst_is_spent() {
+        block_on(async {
+            let mut writer = Throttle::new(Mock::default(), 1000, 100);
+            let started = Instant::now();
+
+            writer.write_all(&[0; 300]).await.unwrap();
+
+            assert_eq!(writer.get_ref().output.len(), 300);
+            assert!(started.elapsed() >= Duration::from_millis(150));
+        });
+    }
+}
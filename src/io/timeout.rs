@@ -0,0 +1,296 @@
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    io,
+    pin::pin,
+    task::Poll,
+    time::{Duration, Instant},
+};
+
+use super::{AsyncBufRead, AsyncRead, AsyncWrite};
+use crate::runtime::pending_until;
+
+pub struct Timeout<T> {
+    inner: T,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    last_activity: Instant,
+}
+
+impl<T> Timeout<T> {
+    pub fn new(inner: T) -> Timeout<T> {
+        Timeout {
+            inner,
+            read_timeout: None,
+            write_timeout: None,
+            idle_timeout: None,
+            last_activity: Instant::now(),
+        }
+    }
+
+    pub fn set_read_timeout(&mut self, dur: Option<Duration>) -> io::Result<()> {
+        self.read_timeout = check_timeout(dur)?;
+
+        Ok(())
+    }
+
+    pub fn set_write_timeout(&mut self, dur: Option<Duration>) -> io::Result<()> {
+        self.write_timeout = check_timeout(dur)?;
+
+        Ok(())
+    }
+
+    pub fn set_idle_timeout(&mut self, dur: Option<Duration>) -> io::Result<()> {
+        self.idle_timeout = check_timeout(dur)?;
+        self.last_activity = Instant::now();
+
+        Ok(())
+    }
+
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    pub fn write_timeout(&self) -> Option<Duration> {
+        self.write_timeout
+    }
+
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Timeout<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timeout")
+            .field("inner", &self.inner)
+            .field("read_timeout", &self.read_timeout)
+            .field("write_timeout", &self.write_timeout)
+            .field("idle_timeout", &self.idle_timeout)
+            .finish()
+    }
+}
+
+fn check_timeout(dur: Option<Duration>) -> io::Result<Option<Duration>> {
+    if dur.is_some_and(|dur| dur.is_zero()) {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Timeout duration can't be zero",
+        ))
+    } else {
+        Ok(dur)
+    }
+}
+
+async fn with_deadline<T>(
+    future: impl Future<Output = io::Result<T>>,
+    timeout: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    last_activity: &mut Instant,
+    operation: &str,
+) -> io::Result<T> {
+    let now = Instant::now();
+    let deadline = timeout.map(|timeout| (now + timeout, operation));
+    let idle_deadline = idle_timeout.map(|timeout| (*last_activity + timeout, "Idle"));
+    let deadline = match (deadline, idle_deadline) {
+        (Some(deadline), Some(idle_deadline)) => Some(deadline.min(idle_deadline)),
+        (deadline, idle_deadline) => deadline.or(idle_deadline),
+    };
+    let mut future = pin!(future);
+
+    let result = poll_fn(|context| {
+        if let Poll::Ready(result) = future.as_mut().poll(context) {
+            return Poll::Ready(result);
+        }
+
+        match deadline {
+            Some((deadline, operation)) if Instant::now() >= deadline => Poll::Ready(Err(
+                io::Error::new(io::ErrorKind::TimedOut, format!("{operation} timed out")),
+            )),
+            Some((deadline, _)) => pending_until(deadline),
+            None => Poll::Pending,
+        }
+    })
+    .await;
+
+    if result.is_ok() {
+        *last_activity = Instant::now();
+    }
+
+    result
+}
+
+impl<T: AsyncRead> AsyncRead for Timeout<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        with_deadline(
+            self.inner.read(buf),
+            self.read_timeout,
+            self.idle_timeout,
+            &mut self.last_activity,
+            "Read",
+        )
+        .await
+    }
+
+    async fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        with_deadline(
+            self.inner.read_vectored(bufs),
+            self.read_timeout,
+            self.idle_timeout,
+            &mut self.last_activity,
+            "Read",
+        )
+        .await
+    }
+}
+
+impl<T: AsyncBufRead> AsyncBufRead for Timeout<T> {
+    async fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        with_deadline(
+            self.inner.fill_buf(),
+            self.read_timeout,
+            self.idle_timeout,
+            &mut self.last_activity,
+            "Read",
+        )
+        .await
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for Timeout<T> {
+    async fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        with_deadline(
+            self.inner.write(buf),
+            self.write_timeout,
+            self.idle_timeout,
+            &mut self.last_activity,
+            "Write",
+        )
+        .await
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        with_deadline(
+            self.inner.flush(),
+            self.write_timeout,
+            self.idle_timeout,
+            &mut self.last_activity,
+            "Write",
+        )
+        .await
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        with_deadline(
+            self.inner.shutdown(),
+            self.write_timeout,
+            self.idle_timeout,
+            &mut self.last_activity,
+            "Write",
+        )
+        .await
+    }
+
+    async fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        with_deadline(
+            self.inner.write_vectored(bufs),
+            self.write_timeout,
+            self.idle_timeout,
+            &mut self.last_activity,
+            "Write",
+        )
+        .await
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{block_on, tcp_pair, Mock};
+
+    #[test]
+    fn read_times_out_when_no_data_arrives() {
+        block_on(async {
+            let (_peer, stream) = tcp_pair().await;
+            let mut stream = Timeout::new(stream);
+            let mut buffer = [0; 8];
+
+            stream
+                .set_read_timeout(Some(Duration::from_millis(50)))
+                .unwrap();
+
+            let started = Instant::now();
+            let error = stream.read(&mut buffer).await.unwrap_err();
+
+            assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+            assert!(started.elapsed() >= Duration::from_millis(50));
+        });
+    }
+
+    #[test]
+    fn idle_timeout_spans_operations_and_resets_on_activity() {
+        block_on(async {
+            let (mut peer, stream) = tcp_pair().await;
+            let mut stream = Timeout::new(stream);
+            let mut buffer = [0; 8];
+
+            stream
+                .set_idle_timeout(Some(Duration::from_millis(100)))
+                .unwrap();
+            peer.write_all(b"ping").await.unwrap();
+
+            assert_eq!(stream.read(&mut buffer).await.unwrap(), 4);
+            stream.write_all(b"pong").await.unwrap();
+
+            let started = Instant::now();
+            let error = stream.read(&mut buffer).await.unwrap_err();
+
+            assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+            assert!(started.elapsed() < Duration::from_secs(1));
+        });
+    }
+
+    #[test]
+    fn ready_operations_and_zero_durations() {
+        block_on(async {
+            let mut stream = Timeout::new(Mock::new([&b"data"[..]]));
+            let mut received = Vec::new();
+
+            assert_eq!(
+                stream
+                    .set_write_timeout(Some(Duration::ZERO))
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::InvalidInput
+            );
+            stream
+                .set_write_timeout(Some(Duration::from_millis(10)))
+                .unwrap();
+            stream.read_to_end(&mut received).await.unwrap();
+            stream.write_all(&received).await.unwrap();
+
+            assert_eq!(stream.get_ref().output, b"data");
+        });
+    }
+}
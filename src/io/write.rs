@@ -1,25 +1,30 @@
 use std::{fmt::Arguments, future::Future, io};
 
-use super::INIT_BUFFER_SIZE;
-
 pub trait AsyncWrite {
     fn write(&mut self, buf: &[u8]) -> impl Future<Output = io::Result<usize>>;
 
     fn flush(&mut self) -> impl Future<Output = io::Result<()>>;
 
-    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> impl Future<Output = io::Result<()>> {
+    fn shutdown(&mut self) -> impl Future<Output = io::Result<()>> {
+        async { self.flush().await }
+    }
+
+    fn write_vectored(
+        &mut self,
+        bufs: &[io::IoSlice<'_>],
+    ) -> impl Future<Output = io::Result<usize>> {
         async {
-            let buffer =
-                bufs.iter()
-                    .fold(Vec::with_capacity(INIT_BUFFER_SIZE), |mut buffer, buf| {
-                        buffer.extend_from_slice(buf);
-                        buffer
-                    });
-
-            self.write_all(&buffer).await
+            match bufs.iter().find(|buf| !buf.is_empty()) {
+                Some(buf) => self.write(buf).await,
+                None => Ok(0),
+            }
         }
     }
 
+    fn is_write_vectored(&self) -> bool {
+        false
+    }
+
     fn write_all(&mut self, buf: &[u8]) -> impl Future<Output = io::Result<()>> {
         async {
             let mut length = 0;
@@ -32,6 +37,28 @@ pub trait AsyncWrite {
         }
     }
 
+    fn write_all_vectored(
+        &mut self,
+        mut bufs: &mut [io::IoSlice<'_>],
+    ) -> impl Future<Output = io::Result<()>> {
+        async move {
+            io::IoSlice::advance_slices(&mut bufs, 0);
+
+            while !bufs.is_empty() {
+                match self.write_vectored(bufs).await {
+                    Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                    Ok(length) => io::IoSlice::advance_slices(&mut bufs, length),
+                    Err(error) => match error.kind() {
+                        io::ErrorKind::Interrupted => {}
+                        _ => return Err(error),
+                    },
+                }
+            }
+
+            Ok(())
+        }
+    }
+
     fn write_fmt(&mut self, fmt: Arguments<'_>) -> impl Future<Output = io::Result<()>> {
         async move { self.write_all(fmt.to_string().as_bytes()).await }
     }
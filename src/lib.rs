@@ -3,7 +3,14 @@ use std::pin::Pin;
 pub mod io;
 pub mod net;
 pub mod runtime;
+pub mod signal;
+pub mod sink;
+pub mod stream;
 pub mod sync;
 pub mod thread;
 
+mod sys;
+#[cfg(test)]
+mod test_util;
+
 pub(crate) type BoxFuture<'a, T> = Pin<Box<dyn std::future::Future<Output = T> + Send + 'a>>;
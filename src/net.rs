@@ -1,10 +1,53 @@
+use std::{
+    future::{poll_fn, Future},
+    io::{Error, ErrorKind, Result},
+    os::fd::RawFd,
+    pin::pin,
+    task::Poll,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    io::{Interest, Ready},
+    runtime::{
+        driver::{driver, Driver},
+        pending_until,
+    },
+};
+
+mod ancillary;
+mod recv_buf;
+mod recv_meta;
+mod resolver;
+mod send_meta;
+mod server;
+mod sockopt;
+mod tcp_keepalive;
 mod tcp_listener;
+mod tcp_socket;
 mod tcp_stream;
+mod to_socket_addrs;
+mod ucred;
 mod udp_socket;
+mod unix_datagram;
+mod unix_listener;
+mod unix_stream;
 
+pub use recv_buf::*;
+pub use recv_meta::*;
+pub use resolver::*;
+pub use send_meta::*;
+pub use server::*;
+pub use tcp_keepalive::*;
 pub use tcp_listener::*;
+pub use tcp_socket::*;
 pub use tcp_stream::*;
+pub use to_socket_addrs::*;
+pub use ucred::*;
 pub use udp_socket::*;
+pub use unix_datagram::*;
+pub use unix_listener::*;
+pub use unix_stream::*;
 
 macro_rules! poll_net {
     ($stream:expr, $timeout:expr, $struct_name:ident::$function_name:ident($($param:expr),*)) => {
@@ -29,7 +72,7 @@ macro_rules! poll_net {
                 match $stream.$function_name($($param,)*) {
                     Ok(length) => Poll::Ready(Ok(length)),
                     Err(error) => match error.kind() {
-                        ErrorKind::WouldBlock => Poll::Pending,
+                        ErrorKind::WouldBlock => $crate::runtime::pending(),
                         _ => Poll::Ready(Err(error)),
                     },
                 }
@@ -39,7 +82,7 @@ macro_rules! poll_net {
             poll_fn(|_context| match $stream.$function_name($($param,)*) {
                 Ok(length) => Poll::Ready(Ok(length)),
                 Err(error) => match error.kind() {
-                    ErrorKind::WouldBlock => Poll::Pending,
+                    ErrorKind::WouldBlock => $crate::runtime::pending(),
                     _ => Poll::Ready(Err(error)),
                 },
             })
@@ -48,4 +91,58 @@ macro_rules! poll_net {
     };
 }
 
-pub(self) use poll_net;
+use poll_net;
+
+async fn with_timeout<T>(
+    timeout: Result<Option<Duration>>,
+    name: &str,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let Ok(Some(duration)) = timeout else {
+        return future.await;
+    };
+
+    if duration.is_zero() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Timeout duration can't be zero",
+        ));
+    }
+
+    let instant = Instant::now() + duration;
+    let mut future = pin!(future);
+
+    poll_fn(|context| {
+        if let Poll::Ready(result) = future.as_mut().poll(context) {
+            return Poll::Ready(result);
+        }
+
+        if instant.checked_duration_since(Instant::now()).is_none() {
+            Poll::Ready(Err(Error::new(
+                ErrorKind::TimedOut,
+                format!("{name} timed out"),
+            )))
+        } else {
+            pending_until(instant)
+        }
+    })
+    .await
+}
+
+async fn wait_ready(fd: RawFd, interest: Interest) -> Result<Ready> {
+    Ok(Ready::from_events(
+        driver().ready(fd, interest.events()).await?,
+    ))
+}
+
+fn try_io<T>(fd: RawFd, interest: Interest, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let result = f();
+
+    if let Err(error) = &result {
+        if error.kind() == ErrorKind::WouldBlock {
+            driver().clear_ready(fd, interest.events());
+        }
+    }
+
+    result
+}
@@ -0,0 +1,281 @@
+use std::{
+    io::{Error, ErrorKind, Result},
+    mem,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    os::fd::{BorrowedFd, FromRawFd, OwnedFd, RawFd},
+    ptr,
+    time::{Duration, SystemTime},
+};
+
+use super::{RecvMeta, SendMeta};
+use crate::sys::{cvt, socket_addr_from_raw, socket_addr_to_raw};
+
+const MAX_FDS: usize = 253;
+const META_CONTROL_LENGTH: usize = 256;
+
+pub(super) fn send_with_fds(fd: RawFd, buf: &[u8], fds: &[BorrowedFd<'_>]) -> Result<usize> {
+    let mut iovec = libc::iovec {
+        iov_base: buf.as_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    let fds_length = mem::size_of_val(fds);
+    let mut control = control_buffer(fds_length);
+    let mut message: libc::msghdr = unsafe { mem::zeroed() };
+
+    message.msg_iov = &mut iovec;
+    message.msg_iovlen = 1;
+
+    if !fds.is_empty() {
+        message.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        message.msg_controllen = unsafe { libc::CMSG_SPACE(fds_length as u32) } as _;
+
+        unsafe {
+            let header = libc::CMSG_FIRSTHDR(&message);
+
+            (*header).cmsg_level = libc::SOL_SOCKET;
+            (*header).cmsg_type = libc::SCM_RIGHTS;
+            (*header).cmsg_len = libc::CMSG_LEN(fds_length as u32) as _;
+
+            ptr::copy_nonoverlapping(
+                fds.as_ptr() as *const u8,
+                libc::CMSG_DATA(header),
+                fds_length,
+            );
+        }
+    }
+
+    cvt(unsafe { libc::sendmsg(fd, &message, libc::MSG_NOSIGNAL) }).map(|length| length as usize)
+}
+
+pub(super) fn recv_with_fds(fd: RawFd, buf: &mut [u8]) -> Result<(usize, Vec<OwnedFd>)> {
+    let mut iovec = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    let mut control = control_buffer(MAX_FDS * mem::size_of::<RawFd>());
+    let mut message: libc::msghdr = unsafe { mem::zeroed() };
+
+    message.msg_iov = &mut iovec;
+    message.msg_iovlen = 1;
+    message.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    message.msg_controllen = mem::size_of_val(control.as_slice()) as _;
+
+    let length = cvt(unsafe { libc::recvmsg(fd, &mut message, libc::MSG_CMSG_CLOEXEC) })?;
+    let mut fds = Vec::new();
+
+    unsafe {
+        let mut header = libc::CMSG_FIRSTHDR(&message);
+
+        while !header.is_null() {
+            if (*header).cmsg_level == libc::SOL_SOCKET && (*header).cmsg_type == libc::SCM_RIGHTS {
+                let data = libc::CMSG_DATA(header) as *const RawFd;
+                let count = ((*header).cmsg_len as usize - libc::CMSG_LEN(0) as usize)
+                    / mem::size_of::<RawFd>();
+
+                for index in 0..count {
+                    fds.push(OwnedFd::from_raw_fd(ptr::read_unaligned(data.add(index))));
+                }
+            }
+
+            header = libc::CMSG_NXTHDR(&message, header);
+        }
+    }
+
+    if message.msg_flags & libc::MSG_CTRUNC != 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Ancillary data truncated, file descriptors were dropped",
+        ));
+    }
+
+    if message.msg_flags & libc::MSG_TRUNC != 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Message truncated, buffer is too small",
+        ));
+    }
+
+    Ok((length as usize, fds))
+}
+
+pub(super) fn recv_msg(fd: RawFd, buf: &mut [u8]) -> Result<RecvMeta> {
+    let mut iovec = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut control = control_buffer(META_CONTROL_LENGTH);
+    let mut message: libc::msghdr = unsafe { mem::zeroed() };
+
+    message.msg_name = ptr::addr_of_mut!(storage) as *mut libc::c_void;
+    message.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    message.msg_iov = &mut iovec;
+    message.msg_iovlen = 1;
+    message.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    message.msg_controllen = mem::size_of_val(control.as_slice()) as _;
+
+    let length = cvt(unsafe { libc::recvmsg(fd, &mut message, 0) })?;
+    let mut meta = RecvMeta {
+        len: length as usize,
+        addr: socket_addr_from_raw(&storage, message.msg_namelen)?,
+        truncated: message.msg_flags & libc::MSG_TRUNC != 0,
+        local_addr: None,
+        interface: None,
+        timestamp: None,
+        ttl: None,
+        ecn: None,
+    };
+
+    unsafe {
+        let mut header = libc::CMSG_FIRSTHDR(&message);
+
+        while !header.is_null() {
+            let data = libc::CMSG_DATA(header);
+
+            match ((*header).cmsg_level, (*header).cmsg_type) {
+                (libc::IPPROTO_IP, libc::IP_PKTINFO) => {
+                    let info = ptr::read_unaligned(data as *const libc::in_pktinfo);
+
+                    meta.local_addr = Some(IpAddr::V4(Ipv4Addr::from(
+                        info.ipi_addr.s_addr.to_ne_bytes(),
+                    )));
+                    meta.interface = Some(info.ipi_ifindex as u32);
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_PKTINFO) => {
+                    let info = ptr::read_unaligned(data as *const libc::in6_pktinfo);
+
+                    meta.local_addr = Some(IpAddr::V6(Ipv6Addr::from(info.ipi6_addr.s6_addr)));
+                    meta.interface = Some(info.ipi6_ifindex);
+                }
+                (libc::SOL_SOCKET, libc::SCM_TIMESTAMPNS) => {
+                    let time = ptr::read_unaligned(data as *const libc::timespec);
+
+                    meta.timestamp = Some(
+                        SystemTime::UNIX_EPOCH
+                            + Duration::new(time.tv_sec as u64, time.tv_nsec as u32),
+                    );
+                }
+                (libc::IPPROTO_IP, libc::IP_TTL) | (libc::IPPROTO_IPV6, libc::IPV6_HOPLIMIT) => {
+                    meta.ttl = Some(ptr::read_unaligned(data as *const libc::c_int) as u8);
+                }
+                (libc::IPPROTO_IP, libc::IP_TOS) => {
+                    meta.ecn = Some(*data & 0x03);
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_TCLASS) => {
+                    meta.ecn = Some(ptr::read_unaligned(data as *const libc::c_int) as u8 & 0x03);
+                }
+                _ => {}
+            }
+
+            header = libc::CMSG_NXTHDR(&message, header);
+        }
+    }
+
+    Ok(meta)
+}
+
+pub(super) fn send_msg(
+    fd: RawFd,
+    buf: &[u8],
+    addr: &SocketAddr,
+    meta: &SendMeta,
+    ipv6: bool,
+    tos: u32,
+) -> Result<usize> {
+    let source = match (meta.source(), ipv6) {
+        (Some(IpAddr::V4(source)), true) => Some(IpAddr::V6(source.to_ipv6_mapped())),
+        (Some(IpAddr::V6(source)), false) => match source.to_ipv4_mapped() {
+            Some(source) => Some(IpAddr::V4(source)),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "IPv6 source address on an IPv4 socket",
+                ))
+            }
+        },
+        (source, _) => source,
+    };
+    let mut iovec = libc::iovec {
+        iov_base: buf.as_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    let (storage, length) = socket_addr_to_raw(addr);
+    let mut control = control_buffer(META_CONTROL_LENGTH);
+    let mut message: libc::msghdr = unsafe { mem::zeroed() };
+    let mut control_length = 0;
+
+    message.msg_name = ptr::addr_of!(storage) as *mut libc::c_void;
+    message.msg_namelen = length;
+    message.msg_iov = &mut iovec;
+    message.msg_iovlen = 1;
+    message.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    message.msg_controllen = mem::size_of_val(control.as_slice()) as _;
+
+    unsafe {
+        let mut header = libc::CMSG_FIRSTHDR(&message);
+
+        if source.is_some() || meta.interface().is_some() {
+            control_length += if ipv6 {
+                let mut info: libc::in6_pktinfo = mem::zeroed();
+
+                if let Some(IpAddr::V6(source)) = source {
+                    info.ipi6_addr.s6_addr = source.octets();
+                }
+
+                info.ipi6_ifindex = meta.interface().unwrap_or(0);
+
+                write_control(header, libc::IPPROTO_IPV6, libc::IPV6_PKTINFO, info)
+            } else {
+                let mut info: libc::in_pktinfo = mem::zeroed();
+
+                if let Some(IpAddr::V4(source)) = source {
+                    info.ipi_spec_dst.s_addr = u32::from_ne_bytes(source.octets());
+                }
+
+                info.ipi_ifindex = meta.interface().unwrap_or(0) as libc::c_int;
+
+                write_control(header, libc::IPPROTO_IP, libc::IP_PKTINFO, info)
+            };
+            header = libc::CMSG_NXTHDR(&message, header);
+        }
+
+        if let Some(ecn) = meta.ecn() {
+            let tos = ((tos & 0xfc) | (ecn as u32 & 0x03)) as libc::c_int;
+
+            control_length += if ipv6 {
+                write_control(header, libc::IPPROTO_IPV6, libc::IPV6_TCLASS, tos)
+            } else {
+                write_control(header, libc::IPPROTO_IP, libc::IP_TOS, tos)
+            };
+        }
+    }
+
+    if control_length == 0 {
+        message.msg_control = ptr::null_mut();
+    }
+
+    message.msg_controllen = control_length as _;
+
+    cvt(unsafe { libc::sendmsg(fd, &message, libc::MSG_NOSIGNAL) }).map(|length| length as usize)
+}
+
+unsafe fn write_control<T>(
+    header: *mut libc::cmsghdr,
+    level: libc::c_int,
+    kind: libc::c_int,
+    value: T,
+) -> usize {
+    (*header).cmsg_level = level;
+    (*header).cmsg_type = kind;
+    (*header).cmsg_len = libc::CMSG_LEN(mem::size_of::<T>() as u32) as _;
+
+    ptr::write_unaligned(libc::CMSG_DATA(header) as *mut T, value);
+
+    libc::CMSG_SPACE(mem::size_of::<T>() as u32) as usize
+}
+
+fn control_buffer(length: usize) -> Vec<u64> {
+    let space = unsafe { libc::CMSG_SPACE(length as u32) } as usize;
+
+    vec![0; space.div_ceil(mem::size_of::<u64>())]
+}
@@ -0,0 +1,62 @@
+use std::{net::SocketAddr, slice::Chunks};
+
+#[derive(Debug)]
+pub struct RecvBuf {
+    pub(super) buffer: Box<[u8]>,
+    pub(super) length: usize,
+    pub(super) addr: Option<SocketAddr>,
+    pub(super) truncated: bool,
+    pub(super) segment_size: Option<usize>,
+}
+
+impl RecvBuf {
+    pub fn new(capacity: usize) -> RecvBuf {
+        RecvBuf {
+            buffer: vec![0; capacity].into_boxed_slice(),
+            length: 0,
+            addr: None,
+            truncated: false,
+            segment_size: None,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.buffer[..self.length]
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn addr(&self) -> Option<SocketAddr> {
+        self.addr
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn segment_size(&self) -> Option<usize> {
+        self.segment_size
+    }
+
+    pub fn segments(&self) -> Chunks<'_, u8> {
+        self.data()
+            .chunks(self.segment_size.unwrap_or(self.length).max(1))
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.length = 0;
+        self.addr = None;
+        self.truncated = false;
+        self.segment_size = None;
+    }
+}
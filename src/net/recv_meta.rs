@@ -0,0 +1,54 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::SystemTime,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecvMeta {
+    pub(super) len: usize,
+    pub(super) addr: SocketAddr,
+    pub(super) truncated: bool,
+    pub(super) local_addr: Option<IpAddr>,
+    pub(super) interface: Option<u32>,
+    pub(super) timestamp: Option<SystemTime>,
+    pub(super) ttl: Option<u8>,
+    pub(super) ecn: Option<u8>,
+}
+
+impl RecvMeta {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn local_addr(&self) -> Option<IpAddr> {
+        self.local_addr
+    }
+
+    pub fn interface(&self) -> Option<u32> {
+        self.interface
+    }
+
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.timestamp
+    }
+
+    pub fn ttl(&self) -> Option<u8> {
+        self.ttl
+    }
+
+    pub fn ecn(&self) -> Option<u8> {
+        self.ecn
+    }
+}
@@ -0,0 +1,435 @@
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    future::{poll_fn, Future},
+    io::{Error, ErrorKind, Result},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    pin::pin,
+    sync::{Arc, OnceLock, RwLock},
+    task::Poll,
+};
+
+use super::{with_timeout, TcpStream, ToSocketAddrs, UdpSocket};
+use crate::{
+    io::{AsyncRead, AsyncWrite},
+    sys::cvt,
+};
+
+mod cache;
+mod hosts;
+mod message;
+mod resolver_config;
+
+pub use resolver_config::*;
+
+use cache::Cache;
+use hosts::parse_hosts;
+use message::{
+    decode_response, encode_query, matches_query, Response, MAX_UDP_SIZE, RCODE_NOERROR,
+    RCODE_NXDOMAIN, TYPE_A, TYPE_AAAA,
+};
+
+const DEFAULT_NAMESERVER: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 53);
+
+struct ResolverInner {
+    config: ResolverConfig,
+    hosts: HashMap<String, Vec<IpAddr>>,
+    cache: Cache,
+}
+
+#[derive(Clone)]
+pub struct Resolver(Arc<ResolverInner>);
+
+impl Resolver {
+    pub fn new(config: ResolverConfig) -> Resolver {
+        let hosts = config
+            .hosts_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| parse_hosts(&contents))
+            .unwrap_or_default();
+
+        Resolver(Arc::new(ResolverInner {
+            config,
+            hosts,
+            cache: Cache::default(),
+        }))
+    }
+
+    pub fn system() -> Result<Resolver> {
+        Ok(Self::new(ResolverConfig::system()?))
+    }
+
+    pub fn config(&self) -> &ResolverConfig {
+        &self.0.config
+    }
+
+    pub fn clear_cache(&self) {
+        self.0.cache.clear()
+    }
+
+    pub async fn lookup_ip(&self, name: &str) -> Result<Vec<IpAddr>> {
+        if let Ok(address) = name.parse::<IpAddr>() {
+            return Ok(vec![address]);
+        }
+
+        let name = name.to_ascii_lowercase();
+        let relative_name = name.strip_suffix('.').unwrap_or(&name);
+
+        if let Some(addresses) = self.0.hosts.get(relative_name) {
+            return Ok(addresses.clone());
+        }
+
+        if relative_name == "localhost" || relative_name.ends_with(".localhost") {
+            return Ok(vec![
+                IpAddr::V6(Ipv6Addr::LOCALHOST),
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+            ]);
+        }
+
+        let mut error = None;
+
+        for candidate in self.candidates(&name) {
+            match self.lookup_candidate(&candidate).await {
+                Ok(addresses) if !addresses.is_empty() => return Ok(addresses),
+                Ok(_) => {}
+                Err(error_) if error_.kind() == ErrorKind::NotFound => {}
+                Err(error_) => error = Some(error_),
+            }
+        }
+
+        Err(error.unwrap_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("No address found for {relative_name}"),
+            )
+        }))
+    }
+
+    fn candidates(&self, name: &str) -> Vec<String> {
+        if let Some(name) = name.strip_suffix('.') {
+            return vec![name.to_string()];
+        }
+
+        let searched = self
+            .0
+            .config
+            .search()
+            .iter()
+            .map(|domain| format!("{name}.{}", domain.trim_end_matches('.')));
+
+        if name.matches('.').count() >= self.0.config.ndots() {
+            [name.to_string()].into_iter().chain(searched).collect()
+        } else {
+            searched.chain([name.to_string()]).collect()
+        }
+    }
+
+    async fn lookup_candidate(&self, name: &str) -> Result<Vec<IpAddr>> {
+        let mut ipv6 = pin!(self.query(name, TYPE_AAAA));
+        let mut ipv4 = pin!(self.query(name, TYPE_A));
+        let mut ipv6_result = None;
+        let mut ipv4_result = None;
+
+        poll_fn(|context| {
+            if ipv6_result.is_none() {
+                if let Poll::Ready(result) = ipv6.as_mut().poll(context) {
+                    ipv6_result = Some(result);
+                }
+            }
+
+            if ipv4_result.is_none() {
+                if let Poll::Ready(result) = ipv4.as_mut().poll(context) {
+                    ipv4_result = Some(result);
+                }
+            }
+
+            if ipv6_result.is_some() && ipv4_result.is_some() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+
+        match (ipv6_result.unwrap(), ipv4_result.unwrap()) {
+            (Ok(mut ipv6), Ok(ipv4)) => {
+                ipv6.extend(ipv4);
+
+                Ok(ipv6)
+            }
+            (Ok(addresses), Err(_)) | (Err(_), Ok(addresses)) => Ok(addresses),
+            (Err(error), Err(_)) => Err(error),
+        }
+    }
+
+    async fn query(&self, name: &str, record_type: u16) -> Result<Vec<IpAddr>> {
+        let config = &self.0.config;
+
+        if config.cache() {
+            match self.0.cache.get(name, record_type) {
+                Some(Some(addresses)) => return Ok(addresses),
+                Some(None) => return Err(not_found(name)),
+                None => {}
+            }
+        }
+
+        let nameservers = match config.nameservers() {
+            [] => &[DEFAULT_NAMESERVER][..],
+            nameservers => nameservers,
+        };
+        let mut error = None;
+
+        for _ in 0..config.attempts() {
+            for nameserver in nameservers {
+                match self.query_nameserver(*nameserver, name, record_type).await {
+                    Ok(response) => match response.rcode {
+                        RCODE_NOERROR => {
+                            let ttl = match response.addresses.is_empty() {
+                                true => response.negative_ttl,
+                                false => response.ttl,
+                            };
+
+                            if let (true, Some(ttl)) = (config.cache(), ttl) {
+                                self.0.cache.insert(
+                                    name,
+                                    record_type,
+                                    response.addresses.clone(),
+                                    ttl,
+                                );
+                            }
+
+                            return Ok(response.addresses);
+                        }
+                        RCODE_NXDOMAIN => {
+                            if let (true, Some(ttl)) = (config.cache(), response.negative_ttl) {
+                                self.0.cache.insert_missing(name, record_type, ttl);
+                            }
+
+                            return Err(not_found(name));
+                        }
+                        rcode => {
+                            error = Some(Error::other(format!(
+                                "Name server {nameserver} returned error code {rcode}"
+                            )))
+                        }
+                    },
+                    Err(error_) => error = Some(error_),
+                }
+            }
+        }
+
+        Err(error.unwrap_or_else(|| Error::other("Name resolution failed")))
+    }
+
+    async fn query_nameserver(
+        &self,
+        nameserver: SocketAddr,
+        name: &str,
+        record_type: u16,
+    ) -> Result<Response> {
+        let query = encode_query(random_id()?, name, record_type)?;
+        let timeout = self.0.config.timeout();
+        let response = with_timeout(Ok(Some(timeout)), "Resolver", async {
+            let socket = UdpSocket::bind(match nameserver {
+                SocketAddr::V4(_) => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+                SocketAddr::V6(_) => SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
+            })?;
+            let mut buffer = vec![0; MAX_UDP_SIZE];
+
+            socket.send_to(&query, nameserver).await?;
+
+            loop {
+                let (length, address) = socket.recv_from(&mut buffer).await?;
+
+                if address == nameserver && matches_query(&buffer[..length], &query) {
+                    break decode_response(&buffer[..length], record_type);
+                }
+            }
+        })
+        .await?;
+
+        if !response.truncated {
+            return Ok(response);
+        }
+
+        with_timeout(Ok(Some(timeout)), "Resolver", async {
+            let mut stream = TcpStream::connect(nameserver).await?;
+            let mut length = [0; 2];
+
+            stream
+                .write_all(&(query.len() as u16).to_be_bytes())
+                .await?;
+            stream.write_all(&query).await?;
+            stream.read_exact(&mut length).await?;
+
+            let mut buffer = vec![0; u16::from_be_bytes(length) as usize];
+
+            stream.read_exact(&mut buffer).await?;
+
+            if !matches_query(&buffer, &query) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Mismatched DNS response",
+                ));
+            }
+
+            decode_response(&buffer, record_type)
+        })
+        .await
+    }
+}
+
+impl fmt::Debug for Resolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resolver")
+            .field("config", &self.0.config)
+            .finish()
+    }
+}
+
+fn global() -> &'static RwLock<Option<Resolver>> {
+    static RESOLVER: OnceLock<RwLock<Option<Resolver>>> = OnceLock::new();
+
+    RESOLVER.get_or_init(|| RwLock::new(None))
+}
+
+pub fn resolver() -> Resolver {
+    if let Some(resolver) = global().read().expect("Resolver is poisoned").as_ref() {
+        return resolver.clone();
+    }
+
+    global()
+        .write()
+        .expect("Resolver is poisoned")
+        .get_or_insert_with(|| {
+            Resolver::system().unwrap_or_else(|_| Resolver::new(ResolverConfig::new()))
+        })
+        .clone()
+}
+
+pub fn set_resolver(resolver: Resolver) {
+    *global().write().expect("Resolver is poisoned") = Some(resolver);
+}
+
+pub async fn lookup_host<T: ToSocketAddrs>(host: T) -> Result<impl Iterator<Item = SocketAddr>> {
+    host.to_socket_addrs().await
+}
+
+fn random_id() -> Result<u16> {
+    let mut id = [0u8; 2];
+
+    cvt(unsafe { libc::getrandom(id.as_mut_ptr() as *mut libc::c_void, id.len(), 0) })?;
+
+    Ok(u16::from_ne_bytes(id))
+}
+
+fn not_found(name: &str) -> Error {
+    Error::new(ErrorKind::NotFound, format!("{name} does not exist"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net,
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::test_util::block_on;
+
+    // Answers `present.test` with 10.0.0.1 (and no AAAA record) and reports
+    // every other name as nonexistent, attaching an SOA record to negative
+    // answers.
+    fn name_server() -> (SocketAddr, Arc<AtomicUsize>) {
+        let socket = net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let address = socket.local_addr().unwrap();
+        let queries = Arc::new(AtomicUsize::new(0));
+        let queries_ = queries.clone();
+
+        socket
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+
+        thread::spawn(move || {
+            let mut buffer = [0; 512];
+
+            while let Ok((length, peer)) = socket.recv_from(&mut buffer) {
+                queries_.fetch_add(1, Ordering::SeqCst);
+
+                let query = &buffer[..length];
+                let question_end = length - 11;
+                let record_type =
+                    u16::from_be_bytes([query[question_end - 4], query[question_end - 3]]);
+                let present = query[12..].starts_with(b"\x07present\x04test\x00");
+                let answer = present && record_type == TYPE_A;
+                let mut response = query[..question_end].to_vec();
+
+                response[2..4]
+                    .copy_from_slice(&(0x8180u16 | if present { 0 } else { 3 }).to_be_bytes());
+                response[6..12].copy_from_slice(&[0, answer as u8, 0, !answer as u8, 0, 0]);
+
+                if answer {
+                    response
+                        .extend_from_slice(&[0xc0, 12, 0, 1, 0, 1, 0, 0, 1, 44, 0, 4, 10, 0, 0, 1]);
+                } else {
+                    response.extend_from_slice(&[0xc0, 12, 0, 6, 0, 1, 0, 0, 14, 16, 0, 24]);
+                    response.extend_from_slice(&[0xc0, 12, 0xc0, 12]);
+                    response.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4]);
+                    response.extend_from_slice(&60u32.to_be_bytes());
+                }
+
+                socket.send_to(&response, peer).unwrap();
+            }
+        });
+
+        (address, queries)
+    }
+
+    fn resolver(nameserver: SocketAddr) -> Resolver {
+        Resolver::new(
+            ResolverConfig::new()
+                .with_nameservers([nameserver])
+                .with_search(Vec::<String>::new())
+                .with_hosts_path(None::<&str>),
+        )
+    }
+
+    #[test]
+    fn nonexistent_names_are_not_found_and_cached() {
+        let (nameserver, queries) = name_server();
+        let resolver = resolver(nameserver);
+
+        block_on(async move {
+            let error = resolver.lookup_ip("missing.test").await.unwrap_err();
+
+            assert_eq!(error.kind(), ErrorKind::NotFound);
+            assert_eq!(queries.load(Ordering::SeqCst), 2);
+
+            let error = resolver.lookup_ip("missing.test").await.unwrap_err();
+
+            assert_eq!(error.kind(), ErrorKind::NotFound);
+            assert_eq!(queries.load(Ordering::SeqCst), 2);
+
+            resolver.clear_cache();
+            resolver.lookup_ip("missing.test").await.unwrap_err();
+
+            assert_eq!(queries.load(Ordering::SeqCst), 4);
+        });
+    }
+
+    #[test]
+    fn missing_record_types_are_cached() {
+        let (nameserver, queries) = name_server();
+        let resolver = resolver(nameserver);
+
+        block_on(async move {
+            let expected = [IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))];
+
+            assert_eq!(resolver.lookup_ip("present.test").await.unwrap(), expected);
+            assert_eq!(resolver.lookup_ip("present.test").await.unwrap(), expected);
+            assert_eq!(queries.load(Ordering::SeqCst), 2);
+        });
+    }
+}
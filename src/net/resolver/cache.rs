@@ -0,0 +1,72 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+const MAX_ENTRIES: usize = 1024;
+
+// `None` records a name that does not exist.
+type Entry = (Option<Vec<IpAddr>>, Instant);
+
+#[derive(Debug, Default)]
+pub(super) struct Cache {
+    entries: Mutex<HashMap<(String, u16), Entry>>,
+}
+
+impl Cache {
+    pub(super) fn get(&self, name: &str, record_type: u16) -> Option<Option<Vec<IpAddr>>> {
+        let mut entries = self.entries.lock().expect("Resolver cache is poisoned");
+        let key = (name.to_string(), record_type);
+
+        match entries.get(&key) {
+            Some((addresses, expiration)) if *expiration > Instant::now() => {
+                Some(addresses.clone())
+            }
+            Some(_) => {
+                entries.remove(&key);
+
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(super) fn insert(&self, name: &str, record_type: u16, addresses: Vec<IpAddr>, ttl: u32) {
+        self.insert_entry(name, record_type, Some(addresses), ttl)
+    }
+
+    pub(super) fn insert_missing(&self, name: &str, record_type: u16, ttl: u32) {
+        self.insert_entry(name, record_type, None, ttl)
+    }
+
+    fn insert_entry(&self, name: &str, record_type: u16, addresses: Option<Vec<IpAddr>>, ttl: u32) {
+        if ttl == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().expect("Resolver cache is poisoned");
+        let now = Instant::now();
+
+        if entries.len() >= MAX_ENTRIES {
+            entries.retain(|_, (_, expiration)| *expiration > now);
+
+            if entries.len() >= MAX_ENTRIES {
+                entries.clear();
+            }
+        }
+
+        entries.insert(
+            (name.to_string(), record_type),
+            (addresses, now + Duration::from_secs(ttl as u64)),
+        );
+    }
+
+    pub(super) fn clear(&self) {
+        self.entries
+            .lock()
+            .expect("Resolver cache is poisoned")
+            .clear();
+    }
+}
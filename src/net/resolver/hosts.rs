@@ -0,0 +1,29 @@
+use std::{collections::HashMap, net::IpAddr};
+
+pub(super) fn parse_hosts(contents: &str) -> HashMap<String, Vec<IpAddr>> {
+    let mut hosts: HashMap<String, Vec<IpAddr>> = HashMap::new();
+
+    for line in contents.lines() {
+        let line = line.split('#').next().unwrap_or_default();
+        let mut fields = line.split_whitespace();
+        let Some(Ok(address)) = fields.next().map(|address| {
+            address
+                .split('%')
+                .next()
+                .unwrap_or_default()
+                .parse::<IpAddr>()
+        }) else {
+            continue;
+        };
+
+        for name in fields {
+            let addresses = hosts.entry(name.to_ascii_lowercase()).or_default();
+
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+    }
+
+    hosts
+}
@@ -0,0 +1,309 @@
+use std::{
+    io::{Error, ErrorKind, Result},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+pub(super) const TYPE_A: u16 = 1;
+pub(super) const TYPE_AAAA: u16 = 28;
+pub(super) const TYPE_SOA: u16 = 6;
+pub(super) const RCODE_NOERROR: u8 = 0;
+pub(super) const RCODE_NXDOMAIN: u8 = 3;
+pub(super) const MAX_UDP_SIZE: usize = 1232;
+
+const TYPE_OPT: u16 = 41;
+const CLASS_IN: u16 = 1;
+const HEADER_LENGTH: usize = 12;
+const OPT_LENGTH: usize = 11;
+const FLAG_RESPONSE: u16 = 0x8000;
+const FLAG_TRUNCATED: u16 = 0x0200;
+const FLAG_RECURSION_DESIRED: u16 = 0x0100;
+
+#[derive(Debug)]
+pub(super) struct Response {
+    pub(super) truncated: bool,
+    pub(super) rcode: u8,
+    pub(super) addresses: Vec<IpAddr>,
+    pub(super) ttl: Option<u32>,
+    pub(super) negative_ttl: Option<u32>,
+}
+
+pub(super) fn encode_query(id: u16, name: &str, record_type: u16) -> Result<Vec<u8>> {
+    let mut message = Vec::with_capacity(HEADER_LENGTH + name.len() + 6 + OPT_LENGTH);
+
+    for field in [id, FLAG_RECURSION_DESIRED, 1, 0, 0, 1] {
+        message.extend_from_slice(&field.to_be_bytes());
+    }
+
+    encode_name(&mut message, name)?;
+    message.extend_from_slice(&record_type.to_be_bytes());
+    message.extend_from_slice(&CLASS_IN.to_be_bytes());
+
+    message.push(0);
+    message.extend_from_slice(&TYPE_OPT.to_be_bytes());
+    message.extend_from_slice(&(MAX_UDP_SIZE as u16).to_be_bytes());
+    message.extend_from_slice(&[0; 6]);
+
+    Ok(message)
+}
+
+pub(super) fn matches_query(response: &[u8], query: &[u8]) -> bool {
+    let question_end = query.len() - OPT_LENGTH;
+
+    response.len() >= question_end
+        && response[..2] == query[..2]
+        && response[HEADER_LENGTH..question_end]
+            .eq_ignore_ascii_case(&query[HEADER_LENGTH..question_end])
+}
+
+pub(super) fn decode_response(message: &[u8], record_type: u16) -> Result<Response> {
+    let mut reader = Reader {
+        message,
+        position: 0,
+    };
+
+    reader.u16()?;
+
+    let flags = reader.u16()?;
+    let questions = reader.u16()?;
+    let answers = reader.u16()?;
+    let authorities = reader.u16()?;
+
+    reader.skip(2)?;
+
+    if flags & FLAG_RESPONSE == 0 {
+        return Err(invalid_response());
+    }
+
+    let mut response = Response {
+        truncated: flags & FLAG_TRUNCATED != 0,
+        rcode: (flags & 0x000f) as u8,
+        addresses: Vec::new(),
+        ttl: None,
+        negative_ttl: None,
+    };
+
+    if response.truncated {
+        return Ok(response);
+    }
+
+    for _ in 0..questions {
+        reader.skip_name()?;
+        reader.skip(4)?;
+    }
+
+    for _ in 0..answers {
+        reader.skip_name()?;
+
+        let kind = reader.u16()?;
+        let class = reader.u16()?;
+        let ttl = reader.u32()?;
+        let length = reader.u16()? as usize;
+        let data = reader.take(length)?;
+
+        if kind != record_type || class != CLASS_IN {
+            continue;
+        }
+
+        match (kind, <[u8; 4]>::try_from(data), <[u8; 16]>::try_from(data)) {
+            (TYPE_A, Ok(octets), _) => response.addresses.push(IpAddr::V4(Ipv4Addr::from(octets))),
+            (TYPE_AAAA, _, Ok(octets)) => {
+                response.addresses.push(IpAddr::V6(Ipv6Addr::from(octets)))
+            }
+            _ => continue,
+        }
+
+        response.ttl = Some(response.ttl.map_or(ttl, |ttl_| ttl_.min(ttl)));
+    }
+
+    for _ in 0..authorities {
+        reader.skip_name()?;
+
+        let kind = reader.u16()?;
+        let class = reader.u16()?;
+        let ttl = reader.u32()?;
+        let length = reader.u16()? as usize;
+        let start = reader.position;
+
+        reader.skip(length)?;
+
+        if kind != TYPE_SOA || class != CLASS_IN {
+            continue;
+        }
+
+        let mut data = Reader {
+            message: &message[..start + length],
+            position: start,
+        };
+
+        data.skip_name()?;
+        data.skip_name()?;
+        data.skip(16)?;
+        response.negative_ttl = Some(ttl.min(data.u32()?));
+    }
+
+    Ok(response)
+}
+
+fn encode_name(message: &mut Vec<u8>, name: &str) -> Result<()> {
+    let name = name.strip_suffix('.').unwrap_or(name);
+
+    if name.is_empty() || name.len() > 253 {
+        return Err(invalid_name());
+    }
+
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(invalid_name());
+        }
+
+        message.push(label.len() as u8);
+        message.extend_from_slice(label.as_bytes());
+    }
+
+    message.push(0);
+
+    Ok(())
+}
+
+fn invalid_name() -> Error {
+    Error::new(ErrorKind::InvalidInput, "Invalid host name")
+}
+
+fn invalid_response() -> Error {
+    Error::new(ErrorKind::InvalidData, "Invalid DNS response")
+}
+
+struct Reader<'a> {
+    message: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8]> {
+        let data = self
+            .message
+            .get(self.position..self.position + length)
+            .ok_or_else(invalid_response)?;
+
+        self.position += length;
+
+        Ok(data)
+    }
+
+    fn skip(&mut self, length: usize) -> Result<()> {
+        self.take(length).map(|_| ())
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let data = self.take(2)?;
+
+        Ok(u16::from_be_bytes([data[0], data[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let data = self.take(4)?;
+
+        Ok(u32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+    }
+
+    fn skip_name(&mut self) -> Result<()> {
+        loop {
+            let length = self.take(1)?[0];
+
+            match length {
+                0 => return Ok(()),
+                length if length & 0xc0 == 0xc0 => return self.skip(1),
+                length if length & 0xc0 == 0 => self.skip(length as usize)?,
+                _ => return Err(invalid_response()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_response(rcode: u16, answers: &[(u16, &[u8])], soa: Option<(u32, u32)>) -> Vec<u8> {
+        let query = encode_query(0x1234, "example.test", TYPE_A).unwrap();
+        let question = &query[HEADER_LENGTH..query.len() - OPT_LENGTH];
+        let mut message = Vec::new();
+
+        for field in [
+            0x1234,
+            FLAG_RESPONSE | FLAG_RECURSION_DESIRED | rcode,
+            1,
+            answers.len() as u16,
+            soa.is_some() as u16,
+            0,
+        ] {
+            message.extend_from_slice(&field.to_be_bytes());
+        }
+
+        message.extend_from_slice(question);
+
+        for (kind, data) in answers {
+            message.extend_from_slice(&[0xc0, HEADER_LENGTH as u8]);
+            message.extend_from_slice(&kind.to_be_bytes());
+            message.extend_from_slice(&CLASS_IN.to_be_bytes());
+            message.extend_from_slice(&300u32.to_be_bytes());
+            message.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            message.extend_from_slice(data);
+        }
+
+        if let Some((ttl, minimum)) = soa {
+            let mut data = vec![0xc0, HEADER_LENGTH as u8, 0xc0, HEADER_LENGTH as u8];
+
+            for field in [1, 2, 3, 4, minimum] {
+                data.extend_from_slice(&u32::to_be_bytes(field));
+            }
+
+            message.extend_from_slice(&[0xc0, HEADER_LENGTH as u8]);
+            message.extend_from_slice(&TYPE_SOA.to_be_bytes());
+            message.extend_from_slice(&CLASS_IN.to_be_bytes());
+            message.extend_from_slice(&ttl.to_be_bytes());
+            message.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            message.extend_from_slice(&data);
+        }
+
+        message
+    }
+
+    #[test]
+    fn decodes_matching_answers() {
+        let query = encode_query(0x1234, "example.test", TYPE_A).unwrap();
+        let message = encode_response(0, &[(TYPE_A, &[10, 0, 0, 1]), (TYPE_AAAA, &[0; 16])], None);
+        let response = decode_response(&message, TYPE_A).unwrap();
+
+        assert!(matches_query(&message, &query));
+        assert_eq!(response.rcode, RCODE_NOERROR);
+        assert_eq!(response.addresses, [IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))]);
+        assert_eq!(response.ttl, Some(300));
+        assert_eq!(response.negative_ttl, None);
+    }
+
+    #[test]
+    fn nxdomain_uses_the_soa_minimum() {
+        let message = encode_response(RCODE_NXDOMAIN as u16, &[], Some((900, 60)));
+        let response = decode_response(&message, TYPE_A).unwrap();
+
+        assert_eq!(response.rcode, RCODE_NXDOMAIN);
+        assert!(response.addresses.is_empty());
+        assert_eq!(response.negative_ttl, Some(60));
+
+        let message = encode_response(RCODE_NXDOMAIN as u16, &[], Some((30, 60)));
+
+        assert_eq!(
+            decode_response(&message, TYPE_A).unwrap().negative_ttl,
+            Some(30)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_names() {
+        assert_eq!(
+            encode_query(1, "bad..name", TYPE_A).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+    }
+}
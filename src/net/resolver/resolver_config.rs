@@ -0,0 +1,178 @@
+use std::{
+    fs,
+    io::{ErrorKind, Result},
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const RESOLV_CONF_PATH: &str = "/etc/resolv.conf";
+const HOSTS_PATH: &str = "/etc/hosts";
+const DNS_PORT: u16 = 53;
+const MAX_NDOTS: usize = 15;
+const MAX_ATTEMPTS: usize = 5;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolverConfig {
+    nameservers: Vec<SocketAddr>,
+    search: Vec<String>,
+    ndots: usize,
+    timeout: Duration,
+    attempts: usize,
+    hosts_path: Option<PathBuf>,
+    cache: bool,
+}
+
+impl ResolverConfig {
+    pub fn new() -> ResolverConfig {
+        ResolverConfig {
+            nameservers: Vec::new(),
+            search: Vec::new(),
+            ndots: 1,
+            timeout: Duration::from_secs(5),
+            attempts: 2,
+            hosts_path: None,
+            cache: true,
+        }
+    }
+
+    pub fn system() -> Result<ResolverConfig> {
+        let config = match fs::read_to_string(RESOLV_CONF_PATH) {
+            Ok(contents) => Self::from_resolv_conf(&contents),
+            Err(error) if error.kind() == ErrorKind::NotFound => Self::new(),
+            Err(error) => return Err(error),
+        };
+
+        Ok(config.with_hosts_path(Some(HOSTS_PATH)))
+    }
+
+    pub fn from_resolv_conf(contents: &str) -> ResolverConfig {
+        let mut config = Self::new();
+
+        for line in contents.lines() {
+            let line = line.split(['#', ';']).next().unwrap_or_default();
+            let mut fields = line.split_whitespace();
+
+            match fields.next() {
+                Some("nameserver") => {
+                    if let Some(Ok(address)) = fields.next().map(|address| {
+                        address
+                            .split('%')
+                            .next()
+                            .unwrap_or_default()
+                            .parse::<IpAddr>()
+                    }) {
+                        config.nameservers.push(SocketAddr::new(address, DNS_PORT));
+                    }
+                }
+                Some("domain") => config.search = fields.take(1).map(str::to_string).collect(),
+                Some("search") => config.search = fields.map(str::to_string).collect(),
+                Some("options") => {
+                    for option in fields {
+                        match option.split_once(':') {
+                            Some(("ndots", value)) => {
+                                if let Ok(ndots) = value.parse::<usize>() {
+                                    config.ndots = ndots.min(MAX_NDOTS);
+                                }
+                            }
+                            Some(("timeout", value)) => {
+                                if let Ok(timeout) = value.parse::<u64>() {
+                                    config.timeout = Duration::from_secs(timeout.max(1));
+                                }
+                            }
+                            Some(("attempts", value)) => {
+                                if let Ok(attempts) = value.parse::<usize>() {
+                                    config.attempts = attempts.clamp(1, MAX_ATTEMPTS);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        config
+    }
+
+    pub fn with_nameservers(
+        self,
+        nameservers: impl IntoIterator<Item = SocketAddr>,
+    ) -> ResolverConfig {
+        ResolverConfig {
+            nameservers: nameservers.into_iter().collect(),
+            ..self
+        }
+    }
+
+    pub fn with_search(
+        self,
+        search: impl IntoIterator<Item = impl Into<String>>,
+    ) -> ResolverConfig {
+        ResolverConfig {
+            search: search.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_ndots(self, ndots: usize) -> ResolverConfig {
+        ResolverConfig { ndots, ..self }
+    }
+
+    pub fn with_timeout(self, timeout: Duration) -> ResolverConfig {
+        ResolverConfig { timeout, ..self }
+    }
+
+    pub fn with_attempts(self, attempts: usize) -> ResolverConfig {
+        ResolverConfig {
+            attempts: attempts.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_hosts_path(self, hosts_path: Option<impl Into<PathBuf>>) -> ResolverConfig {
+        ResolverConfig {
+            hosts_path: hosts_path.map(Into::into),
+            ..self
+        }
+    }
+
+    pub fn with_cache(self, cache: bool) -> ResolverConfig {
+        ResolverConfig { cache, ..self }
+    }
+
+    pub fn nameservers(&self) -> &[SocketAddr] {
+        &self.nameservers
+    }
+
+    pub fn search(&self) -> &[String] {
+        &self.search
+    }
+
+    pub fn ndots(&self) -> usize {
+        self.ndots
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    pub fn hosts_path(&self) -> Option<&Path> {
+        self.hosts_path.as_deref()
+    }
+
+    pub fn cache(&self) -> bool {
+        self.cache
+    }
+}
+
+impl Default for ResolverConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
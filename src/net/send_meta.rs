@@ -0,0 +1,51 @@
+use std::net::IpAddr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SendMeta {
+    source: Option<IpAddr>,
+    interface: Option<u32>,
+    ecn: Option<u8>,
+}
+
+impl SendMeta {
+    pub const fn new() -> SendMeta {
+        SendMeta {
+            source: None,
+            interface: None,
+            ecn: None,
+        }
+    }
+
+    pub const fn with_source(self, source: IpAddr) -> SendMeta {
+        SendMeta {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    pub const fn with_interface(self, interface: u32) -> SendMeta {
+        SendMeta {
+            interface: Some(interface),
+            ..self
+        }
+    }
+
+    pub const fn with_ecn(self, ecn: u8) -> SendMeta {
+        SendMeta {
+            ecn: Some(ecn & 0x03),
+            ..self
+        }
+    }
+
+    pub const fn source(&self) -> Option<IpAddr> {
+        self.source
+    }
+
+    pub const fn interface(&self) -> Option<u32> {
+        self.interface
+    }
+
+    pub const fn ecn(&self) -> Option<u8> {
+        self.ecn
+    }
+}
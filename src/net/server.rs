@@ -0,0 +1,507 @@
+use std::{
+    collections::HashMap,
+    future::{poll_fn, Future},
+    io::{ErrorKind, Result},
+    net::{IpAddr, SocketAddr},
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::Poll,
+    time::{Duration, Instant},
+};
+
+use super::{TcpListener, TcpStream};
+use crate::{
+    runtime::{pending, pending_until},
+    thread::spawn,
+};
+
+const ACCEPT_BACKOFF_MIN: Duration = Duration::from_millis(5);
+const ACCEPT_BACKOFF_MAX: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct Connections {
+    tasks: HashMap<u64, (IpAddr, Arc<AtomicBool>)>,
+    peers: HashMap<IpAddr, usize>,
+}
+
+impl Connections {
+    fn remove(&mut self, id: u64) {
+        let Some((address, _)) = self.tasks.remove(&id) else {
+            return;
+        };
+
+        if let Some(count) = self.peers.get_mut(&address) {
+            *count -= 1;
+
+            if *count == 0 {
+                self.peers.remove(&address);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ServerState {
+    closed: AtomicBool,
+    next_id: AtomicU64,
+    connections: Mutex<Connections>,
+}
+
+#[derive(Clone, Default)]
+pub struct ServerHandle(Arc<ServerState>);
+
+impl ServerHandle {
+    pub fn close(&self) {
+        self.0.closed.store(true, Ordering::Release);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.0.closed.load(Ordering::Acquire)
+    }
+
+    pub fn connections(&self) -> usize {
+        self.0
+            .connections
+            .lock()
+            .expect("Server is poisoned")
+            .tasks
+            .len()
+    }
+
+    pub fn connections_from(&self, address: IpAddr) -> usize {
+        self.0
+            .connections
+            .lock()
+            .expect("Server is poisoned")
+            .peers
+            .get(&address.to_canonical())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub async fn shutdown(&self, grace_period: Duration) -> usize {
+        let deadline = Instant::now() + grace_period;
+
+        self.close();
+
+        poll_fn(|_context| {
+            if self.connections() == 0 || Instant::now() >= deadline {
+                Poll::Ready(())
+            } else {
+                pending()
+            }
+        })
+        .await;
+
+        let connections =
+            std::mem::take(&mut *self.0.connections.lock().expect("Server is poisoned"));
+
+        for (_, aborted) in connections.tasks.values() {
+            aborted.store(true, Ordering::Release);
+        }
+
+        connections.tasks.len()
+    }
+
+    fn track<F>(&self, address: IpAddr, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        let state = self.0.clone();
+        let aborted = Arc::new(AtomicBool::new(false));
+        let mut connections = self.0.connections.lock().expect("Server is poisoned");
+
+        spawn({
+            let aborted = aborted.clone();
+
+            async move {
+                let mut future = pin!(future);
+
+                // An aborted connection is dropped without being polled again,
+                // which closes its stream.
+                poll_fn(|context| match aborted.load(Ordering::Acquire) {
+                    true => Poll::Ready(()),
+                    false => future.as_mut().poll(context),
+                })
+                .await;
+
+                state
+                    .connections
+                    .lock()
+                    .expect("Server is poisoned")
+                    .remove(id);
+            }
+        });
+
+        *connections.peers.entry(address).or_default() += 1;
+        connections.tasks.insert(id, (address, aborted));
+    }
+}
+
+impl std::fmt::Debug for ServerHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerHandle")
+            .field("closed", &self.is_closed())
+            .field("connections", &self.connections())
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    handle: ServerHandle,
+    max_connections: Option<usize>,
+    max_connections_per_ip: Option<usize>,
+}
+
+impl Server {
+    pub fn new(listener: TcpListener) -> Server {
+        Server {
+            listener,
+            handle: ServerHandle::default(),
+            max_connections: None,
+            max_connections_per_ip: None,
+        }
+    }
+
+    pub fn with_max_connections(self, max_connections: usize) -> Server {
+        Server {
+            max_connections: Some(max_connections),
+            ..self
+        }
+    }
+
+    pub fn with_max_connections_per_ip(self, max_connections_per_ip: usize) -> Server {
+        Server {
+            max_connections_per_ip: Some(max_connections_per_ip),
+            ..self
+        }
+    }
+
+    pub fn max_connections(&self) -> Option<usize> {
+        self.max_connections
+    }
+
+    pub fn max_connections_per_ip(&self) -> Option<usize> {
+        self.max_connections_per_ip
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn handle(&self) -> ServerHandle {
+        self.handle.clone()
+    }
+
+    pub async fn serve<H, F>(self, mut handler: H) -> Result<()>
+    where
+        H: FnMut(TcpStream, SocketAddr) -> F,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut backoff = ACCEPT_BACKOFF_MIN;
+        let mut paused_until = None;
+
+        loop {
+            let mut accept = pin!(self.listener.accept());
+            let accepted = poll_fn(|context| {
+                if self.handle.is_closed() {
+                    return Poll::Ready(None);
+                }
+
+                if let Some(until) = paused_until.filter(|until| Instant::now() < *until) {
+                    return pending_until(until);
+                }
+
+                if self
+                    .max_connections
+                    .is_some_and(|max_connections| self.handle.connections() >= max_connections)
+                {
+                    return pending();
+                }
+
+                accept.as_mut().poll(context).map(Some)
+            })
+            .await;
+
+            paused_until = None;
+
+            match accepted {
+                None => return Ok(()),
+                Some(Ok((stream, address))) => {
+                    let ip = address.ip().to_canonical();
+
+                    backoff = ACCEPT_BACKOFF_MIN;
+
+                    if self.max_connections_per_ip.is_some_and(|max_connections| {
+                        self.handle.connections_from(ip) >= max_connections
+                    }) {
+                        continue;
+                    }
+
+                    self.handle.track(ip, handler(stream, address))
+                }
+                Some(Err(error)) if is_transient(&error) => {}
+                Some(Err(error)) if is_exhausted(&error) => {
+                    paused_until = Some(Instant::now() + backoff);
+                    backoff = (backoff * 2).min(ACCEPT_BACKOFF_MAX);
+                }
+                Some(Err(error)) => return Err(error),
+            }
+        }
+    }
+
+    pub async fn serve_with_shutdown<H, F, S>(
+        self,
+        handler: H,
+        signal: S,
+        grace_period: Duration,
+    ) -> Result<usize>
+    where
+        H: FnMut(TcpStream, SocketAddr) -> F,
+        F: Future<Output = ()> + Send + 'static,
+        S: Future<Output = ()>,
+    {
+        let handle = self.handle();
+        let mut serve = pin!(self.serve(handler));
+        let mut signal = pin!(signal);
+        let mut signalled = false;
+
+        let served = poll_fn(|context| {
+            if !signalled && signal.as_mut().poll(context).is_ready() {
+                signalled = true;
+                handle.close();
+            }
+
+            serve.as_mut().poll(context)
+        })
+        .await;
+        let aborted = handle.shutdown(grace_period).await;
+
+        served.map(|()| aborted)
+    }
+}
+
+fn is_transient(error: &std::io::Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::ConnectionAborted | ErrorKind::ConnectionReset | ErrorKind::Interrupted
+    )
+}
+
+fn is_exhausted(error: &std::io::Error) -> bool {
+    matches!(
+        error.raw_os_error(),
+        Some(libc::EMFILE | libc::ENFILE | libc::ENOBUFS | libc::ENOMEM)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, os::fd::AsRawFd};
+
+    use super::*;
+    use crate::{
+        io::{AsyncRead, AsyncWrite},
+        test_util::block_on,
+        thread::{sleep, spawn},
+    };
+
+    async fn wait_for_connections(handle: &ServerHandle, connections: usize) {
+        while handle.connections() < connections {
+            sleep(Duration::from_millis(1)).await;
+        }
+    }
+
+    #[test]
+    fn shutdown_drains_connections_within_the_grace_period() {
+        block_on(async {
+            let server = Server::new(TcpListener::bind("127.0.0.1:0").unwrap());
+            let address = server.local_addr().unwrap();
+            let handle = server.handle();
+            let signal = Arc::new(AtomicBool::new(false));
+            let serving = spawn({
+                let signal = signal.clone();
+
+                server.serve_with_shutdown(
+                    |mut stream, _| async move {
+                        let mut buffer = [0; 4];
+
+                        stream.read_exact(&mut buffer).await.unwrap();
+                        stream.write_all(&buffer).await.unwrap();
+                    },
+                    poll_fn(move |_context| match signal.load(Ordering::Acquire) {
+                        true => Poll::Ready(()),
+                        false => Poll::Pending,
+                    }),
+                    Duration::from_secs(5),
+                )
+            });
+            let mut first = TcpStream::connect(address).await.unwrap();
+            let mut second = TcpStream::connect(address).await.unwrap();
+            let mut buffer = [0; 4];
+
+            wait_for_connections(&handle, 2).await;
+            signal.store(true, Ordering::Release);
+
+            while !handle.is_closed() {
+                sleep(Duration::from_millis(1)).await;
+            }
+
+            for stream in [&mut first, &mut second] {
+                stream.write_all(b"ping").await.unwrap();
+                stream.read_exact(&mut buffer).await.unwrap();
+
+                assert_eq!(&buffer, b"ping");
+            }
+
+            assert_eq!(serving.await.unwrap(), 0);
+            assert_eq!(handle.connections(), 0);
+        });
+    }
+
+    #[test]
+    fn shutdown_aborts_connections_left_after_the_grace_period() {
+        block_on(async {
+            let server = Server::new(TcpListener::bind("127.0.0.1:0").unwrap());
+            let address = server.local_addr().unwrap();
+            let handle = server.handle();
+            let serving = spawn(server.serve(|stream, _| async move {
+                let _stream = stream;
+
+                future::pending::<()>().await
+            }));
+            let mut streams = Vec::new();
+
+            for _ in 0..3 {
+                streams.push(TcpStream::connect(address).await.unwrap());
+            }
+
+            wait_for_connections(&handle, 3).await;
+
+            assert_eq!(handle.shutdown(Duration::from_millis(20)).await, 3);
+            assert_eq!(handle.connections(), 0);
+            assert_eq!(handle.connections_from(address.ip()), 0);
+
+            serving.await.unwrap();
+
+            for mut stream in streams {
+                assert_eq!(stream.read(&mut [0; 1]).await.unwrap(), 0);
+            }
+        });
+    }
+
+    #[test]
+    fn fatal_accept_errors_still_shut_connections_down() {
+        block_on(async {
+            let server = Server::new(TcpListener::bind("127.0.0.1:0").unwrap());
+            let address = server.local_addr().unwrap();
+            let listener = server.listener.as_raw_fd();
+            let handle = server.handle();
+            let serving = spawn(server.serve_with_shutdown(
+                |stream, _| async move {
+                    let _stream = stream;
+
+                    future::pending::<()>().await
+                },
+                future::pending(),
+                Duration::from_millis(20),
+            ));
+            let mut stream = TcpStream::connect(address).await.unwrap();
+
+            wait_for_connections(&handle, 1).await;
+
+            // Accepting on a listener that has been shut down fails with EINVAL.
+            assert_eq!(unsafe { libc::shutdown(listener, libc::SHUT_RD) }, 0);
+
+            assert_eq!(
+                serving.await.unwrap_err().raw_os_error(),
+                Some(libc::EINVAL)
+            );
+            assert_eq!(handle.connections(), 0);
+            assert_eq!(stream.read(&mut [0; 1]).await.unwrap(), 0);
+        });
+    }
+
+    #[test]
+    fn max_connections_pauses_accepting() {
+        block_on(async {
+            let server =
+                Server::new(TcpListener::bind("127.0.0.1:0").unwrap()).with_max_connections(1);
+            let address = server.local_addr().unwrap();
+            let handle = server.handle();
+            let accepted = Arc::new(AtomicU64::new(0));
+            let serving = spawn(server.serve({
+                let accepted = accepted.clone();
+
+                move |mut stream, _| {
+                    accepted.fetch_add(1, Ordering::SeqCst);
+
+                    async move {
+                        stream.read_exact(&mut [0; 1]).await.ok();
+                    }
+                }
+            }));
+            let mut first = TcpStream::connect(address).await.unwrap();
+            let mut second = TcpStream::connect(address).await.unwrap();
+
+            wait_for_connections(&handle, 1).await;
+            sleep(Duration::from_millis(20)).await;
+
+            assert_eq!(accepted.load(Ordering::SeqCst), 1);
+
+            first.write_all(b"x").await.unwrap();
+
+            while accepted.load(Ordering::SeqCst) < 2 {
+                sleep(Duration::from_millis(1)).await;
+            }
+
+            second.write_all(b"x").await.unwrap();
+            handle.shutdown(Duration::from_secs(5)).await;
+            serving.await.unwrap();
+        });
+    }
+
+    #[test]
+    fn connections_over_the_per_ip_cap_are_closed() {
+        block_on(async {
+            let server = Server::new(TcpListener::bind("127.0.0.1:0").unwrap())
+                .with_max_connections_per_ip(1);
+            let address = server.local_addr().unwrap();
+            let handle = server.handle();
+            let serving = spawn(server.serve(|stream, _| async move {
+                let _stream = stream;
+
+                future::pending::<()>().await
+            }));
+            let _first = TcpStream::connect(address).await.unwrap();
+            let mut second = TcpStream::connect(address).await.unwrap();
+
+            assert_eq!(second.read(&mut [0; 1]).await.unwrap(), 0);
+            assert_eq!(handle.connections(), 1);
+            assert_eq!(handle.connections_from(address.ip()), 1);
+
+            assert_eq!(handle.shutdown(Duration::ZERO).await, 1);
+            serving.await.unwrap();
+        });
+    }
+
+    #[test]
+    fn accept_errors_are_classified_for_backoff_and_retry() {
+        for errno in [libc::EMFILE, libc::ENFILE, libc::ENOBUFS, libc::ENOMEM] {
+            assert!(is_exhausted(&std::io::Error::from_raw_os_error(errno)));
+        }
+
+        assert!(!is_exhausted(&std::io::Error::from_raw_os_error(
+            libc::EBADF
+        )));
+        assert!(is_transient(&std::io::Error::from_raw_os_error(
+            libc::ECONNABORTED
+        )));
+    }
+}
@@ -0,0 +1,202 @@
+use std::{io::Result, os::fd::RawFd, time::Duration};
+
+use super::TcpKeepalive;
+use crate::sys::{getsockopt, setsockopt};
+
+pub(super) fn set_bool(
+    fd: RawFd,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: bool,
+) -> Result<()> {
+    setsockopt(fd, level, name, value as libc::c_int)
+}
+
+pub(super) fn bool(fd: RawFd, level: libc::c_int, name: libc::c_int) -> Result<bool> {
+    getsockopt::<libc::c_int>(fd, level, name).map(|value| value != 0)
+}
+
+pub(super) fn set_u32(fd: RawFd, level: libc::c_int, name: libc::c_int, value: u32) -> Result<()> {
+    setsockopt(
+        fd,
+        level,
+        name,
+        value.min(libc::c_int::MAX as u32) as libc::c_int,
+    )
+}
+
+pub(super) fn u32(fd: RawFd, level: libc::c_int, name: libc::c_int) -> Result<u32> {
+    getsockopt::<libc::c_int>(fd, level, name).map(|value| value as u32)
+}
+
+pub(super) fn set_linger(fd: RawFd, linger: Option<Duration>) -> Result<()> {
+    setsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_LINGER,
+        libc::linger {
+            l_onoff: linger.is_some() as libc::c_int,
+            l_linger: linger.map_or(0, |linger| {
+                (linger.as_secs() + (linger.subsec_nanos() > 0) as u64).min(libc::c_int::MAX as u64)
+                    as libc::c_int
+            }),
+        },
+    )
+}
+
+pub(super) fn linger(fd: RawFd) -> Result<Option<Duration>> {
+    let linger: libc::linger = getsockopt(fd, libc::SOL_SOCKET, libc::SO_LINGER)?;
+
+    Ok((linger.l_onoff != 0).then(|| Duration::from_secs(linger.l_linger as u64)))
+}
+
+pub(super) fn set_keepalive(fd: RawFd, keepalive: bool) -> Result<()> {
+    set_bool(fd, libc::SOL_SOCKET, libc::SO_KEEPALIVE, keepalive)
+}
+
+pub(super) fn keepalive(fd: RawFd) -> Result<bool> {
+    bool(fd, libc::SOL_SOCKET, libc::SO_KEEPALIVE)
+}
+
+pub(super) fn set_tcp_keepalive(fd: RawFd, keepalive: &TcpKeepalive) -> Result<()> {
+    set_keepalive(fd, true)?;
+
+    if let Some(time) = keepalive.time() {
+        set_u32(fd, libc::IPPROTO_TCP, libc::TCP_KEEPIDLE, seconds(time))?;
+    }
+
+    if let Some(interval) = keepalive.interval() {
+        set_u32(
+            fd,
+            libc::IPPROTO_TCP,
+            libc::TCP_KEEPINTVL,
+            seconds(interval),
+        )?;
+    }
+
+    if let Some(retries) = keepalive.retries() {
+        set_u32(fd, libc::IPPROTO_TCP, libc::TCP_KEEPCNT, retries)?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn keepalive_time(fd: RawFd) -> Result<Duration> {
+    u32(fd, libc::IPPROTO_TCP, libc::TCP_KEEPIDLE).map(|time| Duration::from_secs(time as u64))
+}
+
+pub(super) fn keepalive_interval(fd: RawFd) -> Result<Duration> {
+    u32(fd, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL)
+        .map(|interval| Duration::from_secs(interval as u64))
+}
+
+pub(super) fn keepalive_retries(fd: RawFd) -> Result<u32> {
+    u32(fd, libc::IPPROTO_TCP, libc::TCP_KEEPCNT)
+}
+
+pub(super) fn set_user_timeout(fd: RawFd, timeout: Option<Duration>) -> Result<()> {
+    set_u32(
+        fd,
+        libc::IPPROTO_TCP,
+        libc::TCP_USER_TIMEOUT,
+        timeout.map_or(0, |timeout| {
+            timeout.as_millis().clamp(1, u32::MAX as u128) as u32
+        }),
+    )
+}
+
+pub(super) fn user_timeout(fd: RawFd) -> Result<Option<Duration>> {
+    u32(fd, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT)
+        .map(|timeout| (timeout != 0).then(|| Duration::from_millis(timeout as u64)))
+}
+
+pub(super) fn set_tos(fd: RawFd, tos: u32) -> Result<()> {
+    let (level, name) = tos_option(fd)?;
+
+    set_u32(fd, level, name, tos)
+}
+
+pub(super) fn tos(fd: RawFd) -> Result<u32> {
+    let (level, name) = tos_option(fd)?;
+
+    u32(fd, level, name)
+}
+
+pub(super) fn set_dscp(fd: RawFd, dscp: u8) -> Result<()> {
+    set_tos(fd, ((dscp as u32 & 0x3f) << 2) | (tos(fd)? & 0x03))
+}
+
+pub(super) fn dscp(fd: RawFd) -> Result<u8> {
+    tos(fd).map(|tos| (tos >> 2) as u8 & 0x3f)
+}
+
+pub(super) fn set_mark(fd: RawFd, mark: u32) -> Result<()> {
+    setsockopt(fd, libc::SOL_SOCKET, libc::SO_MARK, mark)
+}
+
+pub(super) fn mark(fd: RawFd) -> Result<u32> {
+    getsockopt(fd, libc::SOL_SOCKET, libc::SO_MARK)
+}
+
+pub(super) fn is_ipv6(fd: RawFd) -> Result<bool> {
+    getsockopt::<libc::c_int>(fd, libc::SOL_SOCKET, libc::SO_DOMAIN)
+        .map(|domain| domain == libc::AF_INET6)
+}
+
+pub(super) fn set_family_bool(
+    fd: RawFd,
+    ipv4: libc::c_int,
+    ipv6: libc::c_int,
+    value: bool,
+) -> Result<()> {
+    if is_ipv6(fd)? {
+        set_bool(fd, libc::IPPROTO_IPV6, ipv6, value)
+    } else {
+        set_bool(fd, libc::IPPROTO_IP, ipv4, value)
+    }
+}
+
+pub(super) fn family_bool(fd: RawFd, ipv4: libc::c_int, ipv6: libc::c_int) -> Result<bool> {
+    if is_ipv6(fd)? {
+        bool(fd, libc::IPPROTO_IPV6, ipv6)
+    } else {
+        bool(fd, libc::IPPROTO_IP, ipv4)
+    }
+}
+
+fn tos_option(fd: RawFd) -> Result<(libc::c_int, libc::c_int)> {
+    if is_ipv6(fd)? {
+        Ok((libc::IPPROTO_IPV6, libc::IPV6_TCLASS))
+    } else {
+        Ok((libc::IPPROTO_IP, libc::IP_TOS))
+    }
+}
+
+fn seconds(duration: Duration) -> u32 {
+    duration.as_secs().clamp(1, libc::c_int::MAX as u64) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, os::fd::AsRawFd};
+
+    use super::*;
+
+    #[test]
+    fn linger_rounds_sub_second_durations_up() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let fd = listener.as_raw_fd();
+
+        set_linger(fd, Some(Duration::from_millis(500))).unwrap();
+        assert_eq!(linger(fd).unwrap(), Some(Duration::from_secs(1)));
+
+        set_linger(fd, Some(Duration::from_millis(2001))).unwrap();
+        assert_eq!(linger(fd).unwrap(), Some(Duration::from_secs(3)));
+
+        set_linger(fd, Some(Duration::ZERO)).unwrap();
+        assert_eq!(linger(fd).unwrap(), Some(Duration::ZERO));
+
+        set_linger(fd, None).unwrap();
+        assert_eq!(linger(fd).unwrap(), None);
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TcpKeepalive {
+    time: Option<Duration>,
+    interval: Option<Duration>,
+    retries: Option<u32>,
+}
+
+impl TcpKeepalive {
+    pub const fn new() -> TcpKeepalive {
+        TcpKeepalive {
+            time: None,
+            interval: None,
+            retries: None,
+        }
+    }
+
+    pub const fn with_time(self, time: Duration) -> TcpKeepalive {
+        TcpKeepalive {
+            time: Some(time),
+            ..self
+        }
+    }
+
+    pub const fn with_interval(self, interval: Duration) -> TcpKeepalive {
+        TcpKeepalive {
+            interval: Some(interval),
+            ..self
+        }
+    }
+
+    pub const fn with_retries(self, retries: u32) -> TcpKeepalive {
+        TcpKeepalive {
+            retries: Some(retries),
+            ..self
+        }
+    }
+
+    pub const fn time(&self) -> Option<Duration> {
+        self.time
+    }
+
+    pub const fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    pub const fn retries(&self) -> Option<u32> {
+        self.retries
+    }
+}
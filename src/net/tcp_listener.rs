@@ -1,12 +1,16 @@
 use std::{
-    future::poll_fn,
-    io::{Error, ErrorKind, Result},
+    io::{Error, Result},
+    mem,
     net::{self, SocketAddr, ToSocketAddrs},
     os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
-    task::Poll,
+    time::Duration,
 };
 
-use super::TcpStream;
+use super::{sockopt, TcpKeepalive, TcpStream};
+use crate::{
+    runtime::driver::{driver, Driver},
+    stream::Stream,
+};
 
 #[derive(Debug)]
 pub struct TcpListener(net::TcpListener);
@@ -31,14 +35,13 @@ impl TcpListener {
     }
 
     pub async fn accept(&self) -> Result<(TcpStream, SocketAddr)> {
-        poll_fn(|_context| match self.0.accept() {
-            Ok((stream, address)) => Poll::Ready(Ok((TcpStream(stream), address))),
-            Err(error) => match error.kind() {
-                ErrorKind::WouldBlock => Poll::Pending,
-                _ => Poll::Ready(Err(error)),
-            },
-        })
-        .await
+        let (stream, address) = driver().accept(self.as_raw_fd()).await?;
+
+        Ok((TcpStream(net::TcpStream::from(stream)), address))
+    }
+
+    pub fn incoming(&self) -> Incoming<'_> {
+        Incoming { listener: self }
     }
 
     pub fn set_ttl(&self, ttl: u32) -> Result<()> {
@@ -49,11 +52,85 @@ impl TcpListener {
         self.0.ttl()
     }
 
+    pub fn set_keepalive(&self, keepalive: bool) -> Result<()> {
+        sockopt::set_keepalive(self.as_raw_fd(), keepalive)
+    }
+
+    pub fn keepalive(&self) -> Result<bool> {
+        sockopt::keepalive(self.as_raw_fd())
+    }
+
+    pub fn set_tcp_keepalive(&self, keepalive: &TcpKeepalive) -> Result<()> {
+        sockopt::set_tcp_keepalive(self.as_raw_fd(), keepalive)
+    }
+
+    pub fn keepalive_time(&self) -> Result<Duration> {
+        sockopt::keepalive_time(self.as_raw_fd())
+    }
+
+    pub fn keepalive_interval(&self) -> Result<Duration> {
+        sockopt::keepalive_interval(self.as_raw_fd())
+    }
+
+    pub fn keepalive_retries(&self) -> Result<u32> {
+        sockopt::keepalive_retries(self.as_raw_fd())
+    }
+
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        sockopt::set_user_timeout(self.as_raw_fd(), timeout)
+    }
+
+    pub fn user_timeout(&self) -> Result<Option<Duration>> {
+        sockopt::user_timeout(self.as_raw_fd())
+    }
+
+    pub fn set_tos(&self, tos: u32) -> Result<()> {
+        sockopt::set_tos(self.as_raw_fd(), tos)
+    }
+
+    pub fn tos(&self) -> Result<u32> {
+        sockopt::tos(self.as_raw_fd())
+    }
+
+    pub fn set_dscp(&self, dscp: u8) -> Result<()> {
+        sockopt::set_dscp(self.as_raw_fd(), dscp)
+    }
+
+    pub fn dscp(&self) -> Result<u8> {
+        sockopt::dscp(self.as_raw_fd())
+    }
+
+    pub fn set_mark(&self, mark: u32) -> Result<()> {
+        sockopt::set_mark(self.as_raw_fd(), mark)
+    }
+
+    pub fn mark(&self) -> Result<u32> {
+        sockopt::mark(self.as_raw_fd())
+    }
+
     pub fn take_error(&self) -> Result<Option<Error>> {
         self.0.take_error()
     }
 }
 
+#[derive(Debug)]
+pub struct Incoming<'a> {
+    listener: &'a TcpListener,
+}
+
+impl Stream for Incoming<'_> {
+    type Item = Result<TcpStream>;
+
+    async fn next(&mut self) -> Option<Result<TcpStream>> {
+        Some(
+            self.listener
+                .accept()
+                .await
+                .map(|(stream, _address)| stream),
+        )
+    }
+}
+
 impl AsFd for TcpListener {
     fn as_fd(&self) -> BorrowedFd<'_> {
         self.0.as_fd()
@@ -74,7 +151,7 @@ impl From<OwnedFd> for TcpListener {
 
 impl From<TcpListener> for OwnedFd {
     fn from(value: TcpListener) -> Self {
-        OwnedFd::from(value.0)
+        unsafe { OwnedFd::from_raw_fd(value.into_raw_fd()) }
     }
 }
 
@@ -86,6 +163,17 @@ impl FromRawFd for TcpListener {
 
 impl IntoRawFd for TcpListener {
     fn into_raw_fd(self) -> RawFd {
-        self.0.into_raw_fd()
+        let fd = self.as_raw_fd();
+
+        driver().deregister(fd).ok();
+        mem::forget(self);
+
+        fd
+    }
+}
+
+impl Drop for TcpListener {
+    fn drop(&mut self) {
+        driver().deregister(self.as_raw_fd()).ok();
     }
 }
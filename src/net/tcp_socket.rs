@@ -0,0 +1,348 @@
+use std::{
+    ffi::CStr,
+    io::{Error, Result},
+    mem,
+    net::SocketAddr,
+    os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+    ptr,
+    time::Duration,
+};
+
+use super::{sockopt, TcpKeepalive, TcpListener, TcpStream};
+use crate::sys::{cvt, getsockopt, setsockopt, socket_addr_from_raw, socket_addr_to_raw};
+
+#[derive(Debug)]
+pub struct TcpSocket(OwnedFd);
+
+impl TcpSocket {
+    pub fn new_v4() -> Result<TcpSocket> {
+        Self::new(libc::AF_INET)
+    }
+
+    pub fn new_v6() -> Result<TcpSocket> {
+        Self::new(libc::AF_INET6)
+    }
+
+    fn new(domain: libc::c_int) -> Result<TcpSocket> {
+        let socket = cvt(unsafe {
+            libc::socket(
+                domain,
+                libc::SOCK_STREAM | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                0,
+            )
+        })?;
+
+        Ok(TcpSocket(unsafe { OwnedFd::from_raw_fd(socket) }))
+    }
+
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> Result<()> {
+        sockopt::set_bool(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_REUSEADDR,
+            reuseaddr,
+        )
+    }
+
+    pub fn reuseaddr(&self) -> Result<bool> {
+        sockopt::bool(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_REUSEADDR)
+    }
+
+    pub fn set_reuseport(&self, reuseport: bool) -> Result<()> {
+        sockopt::set_bool(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_REUSEPORT,
+            reuseport,
+        )
+    }
+
+    pub fn reuseport(&self) -> Result<bool> {
+        sockopt::bool(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_REUSEPORT)
+    }
+
+    pub fn set_send_buffer_size(&self, size: u32) -> Result<()> {
+        setsockopt(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_SNDBUF,
+            size as libc::c_int,
+        )
+    }
+
+    pub fn send_buffer_size(&self) -> Result<u32> {
+        getsockopt::<libc::c_int>(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDBUF)
+            .map(|size| size as u32)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: u32) -> Result<()> {
+        setsockopt(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RCVBUF,
+            size as libc::c_int,
+        )
+    }
+
+    pub fn recv_buffer_size(&self) -> Result<u32> {
+        getsockopt::<libc::c_int>(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVBUF)
+            .map(|size| size as u32)
+    }
+
+    pub fn bind_device(&self, interface: Option<&[u8]>) -> Result<()> {
+        let interface = interface.unwrap_or_default();
+
+        cvt(unsafe {
+            libc::setsockopt(
+                self.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_BINDTODEVICE,
+                interface.as_ptr() as *const libc::c_void,
+                interface.len() as libc::socklen_t,
+            )
+        })?;
+
+        Ok(())
+    }
+
+    pub fn device(&self) -> Result<Option<Vec<u8>>> {
+        let mut interface = [0u8; libc::IFNAMSIZ];
+        let mut length = interface.len() as libc::socklen_t;
+
+        cvt(unsafe {
+            libc::getsockopt(
+                self.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_BINDTODEVICE,
+                interface.as_mut_ptr() as *mut libc::c_void,
+                &mut length,
+            )
+        })?;
+
+        if length == 0 {
+            return Ok(None);
+        }
+
+        let interface = &interface[..length as usize];
+
+        Ok(Some(
+            CStr::from_bytes_until_nul(interface)
+                .map_or(interface, CStr::to_bytes)
+                .to_vec(),
+        ))
+    }
+
+    pub fn set_linger(&self, linger: Option<Duration>) -> Result<()> {
+        sockopt::set_linger(self.as_raw_fd(), linger)
+    }
+
+    pub fn linger(&self) -> Result<Option<Duration>> {
+        sockopt::linger(self.as_raw_fd())
+    }
+
+    pub fn set_nodelay(&self, nodelay: bool) -> Result<()> {
+        sockopt::set_bool(
+            self.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_NODELAY,
+            nodelay,
+        )
+    }
+
+    pub fn nodelay(&self) -> Result<bool> {
+        sockopt::bool(self.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_NODELAY)
+    }
+
+    pub fn set_keepalive(&self, keepalive: bool) -> Result<()> {
+        sockopt::set_keepalive(self.as_raw_fd(), keepalive)
+    }
+
+    pub fn keepalive(&self) -> Result<bool> {
+        sockopt::keepalive(self.as_raw_fd())
+    }
+
+    pub fn set_tcp_keepalive(&self, keepalive: &TcpKeepalive) -> Result<()> {
+        sockopt::set_tcp_keepalive(self.as_raw_fd(), keepalive)
+    }
+
+    pub fn keepalive_time(&self) -> Result<Duration> {
+        sockopt::keepalive_time(self.as_raw_fd())
+    }
+
+    pub fn keepalive_interval(&self) -> Result<Duration> {
+        sockopt::keepalive_interval(self.as_raw_fd())
+    }
+
+    pub fn keepalive_retries(&self) -> Result<u32> {
+        sockopt::keepalive_retries(self.as_raw_fd())
+    }
+
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        sockopt::set_user_timeout(self.as_raw_fd(), timeout)
+    }
+
+    pub fn user_timeout(&self) -> Result<Option<Duration>> {
+        sockopt::user_timeout(self.as_raw_fd())
+    }
+
+    pub fn set_tos(&self, tos: u32) -> Result<()> {
+        sockopt::set_tos(self.as_raw_fd(), tos)
+    }
+
+    pub fn tos(&self) -> Result<u32> {
+        sockopt::tos(self.as_raw_fd())
+    }
+
+    pub fn set_dscp(&self, dscp: u8) -> Result<()> {
+        sockopt::set_dscp(self.as_raw_fd(), dscp)
+    }
+
+    pub fn dscp(&self) -> Result<u8> {
+        sockopt::dscp(self.as_raw_fd())
+    }
+
+    pub fn set_mark(&self, mark: u32) -> Result<()> {
+        sockopt::set_mark(self.as_raw_fd(), mark)
+    }
+
+    pub fn mark(&self) -> Result<u32> {
+        sockopt::mark(self.as_raw_fd())
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut length = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+        cvt(unsafe {
+            libc::getsockname(
+                self.as_raw_fd(),
+                ptr::addr_of_mut!(storage) as *mut libc::sockaddr,
+                &mut length,
+            )
+        })?;
+
+        socket_addr_from_raw(&storage, length)
+    }
+
+    pub fn take_error(&self) -> Result<Option<Error>> {
+        let error: libc::c_int = getsockopt(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_ERROR)?;
+
+        Ok((error != 0).then(|| Error::from_raw_os_error(error)))
+    }
+
+    pub fn bind(&self, addr: SocketAddr) -> Result<()> {
+        let (storage, length) = socket_addr_to_raw(&addr);
+
+        cvt(unsafe {
+            libc::bind(
+                self.as_raw_fd(),
+                ptr::addr_of!(storage) as *const libc::sockaddr,
+                length,
+            )
+        })?;
+
+        Ok(())
+    }
+
+    pub fn listen(self, backlog: u32) -> Result<TcpListener> {
+        cvt(unsafe {
+            libc::listen(
+                self.as_raw_fd(),
+                backlog.min(i32::MAX as u32) as libc::c_int,
+            )
+        })?;
+
+        Ok(TcpListener::from(self.0))
+    }
+
+    pub async fn connect(self, addr: SocketAddr) -> Result<TcpStream> {
+        TcpStream::connect_socket(self.0, &addr).await
+    }
+}
+
+impl AsFd for TcpSocket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl From<OwnedFd> for TcpSocket {
+    fn from(value: OwnedFd) -> Self {
+        TcpSocket(value)
+    }
+}
+
+impl From<TcpSocket> for OwnedFd {
+    fn from(value: TcpSocket) -> Self {
+        value.0
+    }
+}
+
+impl FromRawFd for TcpSocket {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        TcpSocket(OwnedFd::from_raw_fd(fd))
+    }
+}
+
+impl IntoRawFd for TcpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{io::AsyncWrite, test_util::block_on};
+
+    #[test]
+    fn reuseport_listeners_share_a_port() {
+        block_on(async {
+            let first = TcpSocket::new_v4().unwrap();
+
+            first.set_reuseport(true).unwrap();
+            first.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+
+            let address = first.local_addr().unwrap();
+            let second = TcpSocket::new_v4().unwrap();
+
+            second.set_reuseport(true).unwrap();
+            second.bind(address).unwrap();
+            assert!(first.reuseport().unwrap());
+
+            let _first = first.listen(16).unwrap();
+            let second = second.listen(16).unwrap();
+
+            assert_eq!(second.local_addr().unwrap(), address);
+        });
+    }
+
+    #[test]
+    fn options_apply_before_connect() {
+        block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let socket = TcpSocket::new_v4().unwrap();
+
+            socket.set_nodelay(true).unwrap();
+            socket.set_recv_buffer_size(64 * 1024).unwrap();
+
+            let receive_buffer = socket.recv_buffer_size().unwrap();
+            let mut stream = socket
+                .connect(listener.local_addr().unwrap())
+                .await
+                .unwrap();
+            let (_server, peer) = listener.accept().await.unwrap();
+
+            assert!(stream.nodelay().unwrap());
+            assert!(receive_buffer >= 64 * 1024);
+            assert_eq!(peer, stream.local_addr().unwrap());
+            stream.write_all(b"ping").await.unwrap();
+        });
+    }
+}
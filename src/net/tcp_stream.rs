@@ -1,72 +1,133 @@
 use std::{
     collections::VecDeque,
-    future::poll_fn,
-    io::{Error, ErrorKind, Read, Result, Write},
-    net::{self, Shutdown, SocketAddr, ToSocketAddrs},
-    os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+    fs::File,
+    future::{poll_fn, Future},
+    io::{Error, ErrorKind, IoSlice, IoSliceMut, Read, Result, Write},
+    mem,
+    net::{self, Shutdown, SocketAddr},
+    os::{
+        fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+        unix::fs::FileExt,
+    },
+    pin::Pin,
+    ptr,
     task::Poll,
     time::{Duration, Instant},
 };
 
 use crate::{
-    io::{AsyncRead, AsyncWrite},
-    net::poll_net,
+    io::{AsyncRead, AsyncWrite, Interest, Ready, INIT_BUFFER_SIZE},
+    net::{sockopt, try_io, wait_ready, with_timeout, TcpKeepalive, ToSocketAddrs},
+    runtime::{
+        driver::{driver, Driver, WRITABLE},
+        pending_until,
+    },
+    sys::{cvt, is_unsupported, socket_addr_to_raw},
 };
 
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
 #[derive(Debug)]
 pub struct TcpStream(pub(crate) net::TcpStream);
 
 impl TcpStream {
     pub async fn connect<A: ToSocketAddrs>(addr: A) -> Result<TcpStream> {
-        Self::connect_timeout(addr, Duration::from_secs(1)).await
-    }
-
-    pub async fn connect_timeout<A: ToSocketAddrs>(
-        addr: A,
-        max_timeout: Duration,
-    ) -> Result<TcpStream> {
-        let mut addresses: VecDeque<SocketAddr> = addr.to_socket_addrs()?.collect();
+        let mut addresses = interleave_families(addr.to_socket_addrs().await?);
+        let mut attempts: Vec<Pin<Box<dyn Future<Output = Result<TcpStream>> + Send>>> = Vec::new();
+        let mut next_attempt = Instant::now();
         let mut error = None;
-        let mut timeout = Duration::from_millis(50);
-
-        poll_fn(|_context| {
-            if addresses.is_empty() {
-                Poll::Ready(if let Some(error) = error.take() {
-                    Err(error)
-                } else {
-                    Err(Error::new(
-                        ErrorKind::AddrNotAvailable,
-                        "No SocketAddr provided",
-                    ))
-                })
-            } else {
-                let address = addresses.pop_front().unwrap();
-
-                match net::TcpStream::connect_timeout(&address, timeout) {
-                    Ok(stream) => {
-                        if let Err(error) = stream.set_nonblocking(true) {
-                            Poll::Ready(Err(error))
-                        } else {
-                            Poll::Ready(Ok(TcpStream(stream)))
-                        }
-                    }
-                    Err(error_) => {
-                        if let ErrorKind::TimedOut = error_.kind() {
-                            timeout = (timeout * 2).min(max_timeout);
 
-                            addresses.push_back(address);
-                        } else {
-                            error = Some(error_);
-                        }
+        poll_fn(|context| loop {
+            let mut index = 0;
+
+            while index < attempts.len() {
+                match attempts[index].as_mut().poll(context) {
+                    Poll::Ready(Ok(stream)) => return Poll::Ready(Ok(stream)),
+                    Poll::Ready(Err(error_)) => {
+                        error = Some(error_);
+                        next_attempt = Instant::now();
 
-                        Poll::Pending
+                        drop(attempts.swap_remove(index));
                     }
+                    Poll::Pending => index += 1,
+                }
+            }
+
+            if Instant::now() >= next_attempt {
+                if let Some(address) = addresses.pop_front() {
+                    attempts.push(Box::pin(Self::connect_addr(address)));
+                    next_attempt = Instant::now() + CONNECTION_ATTEMPT_DELAY;
+
+                    continue;
                 }
             }
+
+            return if attempts.is_empty() && addresses.is_empty() {
+                Poll::Ready(Err(error.take().unwrap_or_else(|| {
+                    Error::new(ErrorKind::AddrNotAvailable, "No SocketAddr provided")
+                })))
+            } else if !addresses.is_empty() {
+                pending_until(next_attempt)
+            } else {
+                Poll::Pending
+            };
         })
         .await
     }
 
+    pub async fn connect_timeout<A: ToSocketAddrs>(
+        addr: A,
+        timeout: Duration,
+    ) -> Result<TcpStream> {
+        with_timeout(Ok(Some(timeout)), "TcpStream", Self::connect(addr)).await
+    }
+
+    async fn connect_addr(addr: SocketAddr) -> Result<TcpStream> {
+        let domain = match addr {
+            SocketAddr::V4(_) => libc::AF_INET,
+            SocketAddr::V6(_) => libc::AF_INET6,
+        };
+        let socket = cvt(unsafe {
+            libc::socket(
+                domain,
+                libc::SOCK_STREAM | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                0,
+            )
+        })?;
+
+        Self::connect_socket(unsafe { OwnedFd::from_raw_fd(socket) }, &addr).await
+    }
+
+    pub(crate) async fn connect_socket(socket: OwnedFd, addr: &SocketAddr) -> Result<TcpStream> {
+        let (storage, length) = socket_addr_to_raw(addr);
+        let fd = socket.as_raw_fd();
+        let stream = TcpStream(net::TcpStream::from(socket));
+
+        match cvt(unsafe {
+            libc::connect(fd, ptr::addr_of!(storage) as *const libc::sockaddr, length)
+        }) {
+            Ok(_) => return Ok(stream),
+            Err(error) if matches!(error.raw_os_error(), Some(libc::EINPROGRESS | libc::EINTR)) => {
+            }
+            Err(error) => return Err(error),
+        }
+
+        loop {
+            driver().clear_ready(fd, WRITABLE);
+            driver().ready(fd, WRITABLE).await?;
+
+            if let Some(error) = stream.take_error()? {
+                return Err(error);
+            }
+
+            match stream.peer_addr() {
+                Ok(_) => return Ok(stream),
+                Err(error) if error.raw_os_error() == Some(libc::ENOTCONN) => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
     pub fn peer_addr(&self) -> Result<SocketAddr> {
         self.0.peer_addr()
     }
@@ -100,7 +161,12 @@ impl TcpStream {
     }
 
     pub async fn peek(&self, buf: &mut [u8]) -> Result<usize> {
-        poll_net!(self.0, self.read_timeout(), TcpStream::peek(buf))
+        with_timeout(
+            self.read_timeout(),
+            "TcpStream",
+            driver().peek(self.as_raw_fd(), buf),
+        )
+        .await
     }
 
     pub fn set_nodelay(&self, nodelay: bool) -> Result<()> {
@@ -115,9 +181,185 @@ impl TcpStream {
         self.0.ttl()
     }
 
+    pub fn nodelay(&self) -> Result<bool> {
+        sockopt::bool(self.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_NODELAY)
+    }
+
+    pub fn set_keepalive(&self, keepalive: bool) -> Result<()> {
+        sockopt::set_keepalive(self.as_raw_fd(), keepalive)
+    }
+
+    pub fn keepalive(&self) -> Result<bool> {
+        sockopt::keepalive(self.as_raw_fd())
+    }
+
+    pub fn set_tcp_keepalive(&self, keepalive: &TcpKeepalive) -> Result<()> {
+        sockopt::set_tcp_keepalive(self.as_raw_fd(), keepalive)
+    }
+
+    pub fn keepalive_time(&self) -> Result<Duration> {
+        sockopt::keepalive_time(self.as_raw_fd())
+    }
+
+    pub fn keepalive_interval(&self) -> Result<Duration> {
+        sockopt::keepalive_interval(self.as_raw_fd())
+    }
+
+    pub fn keepalive_retries(&self) -> Result<u32> {
+        sockopt::keepalive_retries(self.as_raw_fd())
+    }
+
+    pub fn set_linger(&self, linger: Option<Duration>) -> Result<()> {
+        sockopt::set_linger(self.as_raw_fd(), linger)
+    }
+
+    pub fn linger(&self) -> Result<Option<Duration>> {
+        sockopt::linger(self.as_raw_fd())
+    }
+
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        sockopt::set_user_timeout(self.as_raw_fd(), timeout)
+    }
+
+    pub fn user_timeout(&self) -> Result<Option<Duration>> {
+        sockopt::user_timeout(self.as_raw_fd())
+    }
+
+    pub fn set_quickack(&self, quickack: bool) -> Result<()> {
+        sockopt::set_bool(
+            self.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_QUICKACK,
+            quickack,
+        )
+    }
+
+    pub fn quickack(&self) -> Result<bool> {
+        sockopt::bool(self.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_QUICKACK)
+    }
+
+    pub fn set_cork(&self, cork: bool) -> Result<()> {
+        sockopt::set_bool(self.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_CORK, cork)
+    }
+
+    pub fn cork(&self) -> Result<bool> {
+        sockopt::bool(self.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_CORK)
+    }
+
+    pub fn set_tos(&self, tos: u32) -> Result<()> {
+        sockopt::set_tos(self.as_raw_fd(), tos)
+    }
+
+    pub fn tos(&self) -> Result<u32> {
+        sockopt::tos(self.as_raw_fd())
+    }
+
+    pub fn set_dscp(&self, dscp: u8) -> Result<()> {
+        sockopt::set_dscp(self.as_raw_fd(), dscp)
+    }
+
+    pub fn dscp(&self) -> Result<u8> {
+        sockopt::dscp(self.as_raw_fd())
+    }
+
+    pub fn set_mark(&self, mark: u32) -> Result<()> {
+        sockopt::set_mark(self.as_raw_fd(), mark)
+    }
+
+    pub fn mark(&self) -> Result<u32> {
+        sockopt::mark(self.as_raw_fd())
+    }
+
     pub fn take_error(&self) -> Result<Option<Error>> {
         self.0.take_error()
     }
+
+    pub async fn ready(&self, interest: Interest) -> Result<Ready> {
+        wait_ready(self.as_raw_fd(), interest).await
+    }
+
+    pub async fn readable(&self) -> Result<()> {
+        self.ready(Interest::READABLE).await?;
+
+        Ok(())
+    }
+
+    pub async fn writable(&self) -> Result<()> {
+        self.ready(Interest::WRITABLE).await?;
+
+        Ok(())
+    }
+
+    pub fn try_read(&self, buf: &mut [u8]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::READABLE, || (&self.0).read(buf))
+    }
+
+    pub fn try_read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::READABLE, || {
+            (&self.0).read_vectored(bufs)
+        })
+    }
+
+    pub fn try_write(&self, buf: &[u8]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::WRITABLE, || {
+            (&self.0).write(buf)
+        })
+    }
+
+    pub fn try_write_vectored(&self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::WRITABLE, || {
+            (&self.0).write_vectored(bufs)
+        })
+    }
+
+    pub async fn sendfile(&self, file: &File, offset: u64, len: usize) -> Result<usize> {
+        with_timeout(self.write_timeout(), "TcpStream", async {
+            let fd = self.as_raw_fd();
+            let mut offset_ = offset as libc::off_t;
+            let mut total = 0;
+
+            while total < len {
+                match driver()
+                    .io(fd, WRITABLE, || {
+                        cvt(unsafe {
+                            libc::sendfile(fd, file.as_raw_fd(), &mut offset_, len - total)
+                        })
+                    })
+                    .await
+                {
+                    Ok(0) => break,
+                    Ok(length) => total += length as usize,
+                    Err(error) if total == 0 && is_unsupported(&error) => {
+                        return self.sendfile_buffered(file, offset, len).await;
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+
+            Ok(total)
+        })
+        .await
+    }
+
+    async fn sendfile_buffered(&self, file: &File, mut offset: u64, len: usize) -> Result<usize> {
+        let mut buffer = [0u8; INIT_BUFFER_SIZE];
+        let mut total = 0;
+
+        while total < len {
+            let length =
+                file.read_at(&mut buffer[..(len - total).min(INIT_BUFFER_SIZE)], offset)?;
+
+            if length == 0 {
+                break;
+            }
+
+            (&*self).write_all(&buffer[..length]).await?;
+            offset += length as u64;
+            total += length;
+        }
+
+        Ok(total)
+    }
 }
 
 impl AsFd for TcpStream {
@@ -140,7 +382,7 @@ impl From<OwnedFd> for TcpStream {
 
 impl From<TcpStream> for OwnedFd {
     fn from(value: TcpStream) -> Self {
-        OwnedFd::from(value.0)
+        unsafe { OwnedFd::from_raw_fd(value.into_raw_fd()) }
     }
 }
 
@@ -152,17 +394,38 @@ impl FromRawFd for TcpStream {
 
 impl IntoRawFd for TcpStream {
     fn into_raw_fd(self) -> RawFd {
-        self.0.into_raw_fd()
+        let fd = self.as_raw_fd();
+
+        driver().deregister(fd).ok();
+        mem::forget(self);
+
+        fd
+    }
+}
+
+impl Drop for TcpStream {
+    fn drop(&mut self) {
+        driver().deregister(self.as_raw_fd()).ok();
     }
 }
 
 impl AsyncRead for &TcpStream {
     async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        poll_net!(
-            self.0.try_clone()?,
+        with_timeout(
+            self.read_timeout(),
+            "TcpStream",
+            driver().recv(self.as_raw_fd(), buf),
+        )
+        .await
+    }
+
+    async fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        with_timeout(
             self.read_timeout(),
-            TcpStream::read(buf)
+            "TcpStream",
+            driver().recv_vectored(self.as_raw_fd(), bufs),
         )
+        .await
     }
 }
 
@@ -170,23 +433,43 @@ impl AsyncRead for TcpStream {
     async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         (&*self).read(buf).await
     }
+
+    async fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        (&*self).read_vectored(bufs).await
+    }
 }
 
 impl AsyncWrite for &TcpStream {
     async fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        poll_net!(
-            self.0.try_clone()?,
+        with_timeout(
             self.write_timeout(),
-            TcpStream::write(buf)
+            "TcpStream",
+            driver().send(self.as_raw_fd(), buf),
         )
+        .await
     }
 
     async fn flush(&mut self) -> Result<()> {
-        poll_net!(
-            self.0.try_clone()?,
+        // Writes go straight to the socket, there is nothing buffered here.
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        self.flush().await?;
+        self.0.shutdown(Shutdown::Write)
+    }
+
+    async fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        with_timeout(
             self.write_timeout(),
-            TcpStream::flush()
+            "TcpStream",
+            driver().send_vectored(self.as_raw_fd(), bufs),
         )
+        .await
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
     }
 }
 
@@ -198,4 +481,187 @@ impl AsyncWrite for TcpStream {
     async fn flush(&mut self) -> Result<()> {
         (&*self).flush().await
     }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        AsyncWrite::shutdown(&mut &*self).await
+    }
+
+    async fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        (&*self).write_vectored(bufs).await
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+}
+
+fn interleave_families(addresses: impl Iterator<Item = SocketAddr>) -> VecDeque<SocketAddr> {
+    let mut addresses = addresses.peekable();
+    let Some(first) = addresses.peek().copied() else {
+        return VecDeque::new();
+    };
+    let (mut preferred, mut fallback): (VecDeque<_>, VecDeque<_>) =
+        addresses.partition(|address| address.is_ipv4() == first.is_ipv4());
+    let mut interleaved = VecDeque::with_capacity(preferred.len() + fallback.len());
+
+    loop {
+        match (preferred.pop_front(), fallback.pop_front()) {
+            (None, None) => return interleaved,
+            (preferred, fallback) => interleaved.extend(preferred.into_iter().chain(fallback)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        net::TcpListener,
+        runtime::driver::READABLE,
+        test_util::{block_on, tcp_pair},
+    };
+
+    #[test]
+    fn dropping_a_stream_deregisters_it() {
+        block_on(async {
+            let (mut client, server) = tcp_pair().await;
+            let fd = server.as_raw_fd();
+
+            client.write_all(b"ping").await.unwrap();
+            driver().ready(fd, READABLE).await.unwrap();
+
+            assert!(driver().is_tracked(fd));
+            drop(server);
+            assert!(!driver().is_tracked(fd));
+        });
+    }
+
+    #[test]
+    fn vectored_io_spans_every_buffer() {
+        block_on(async {
+            let (mut client, mut server) = tcp_pair().await;
+            let mut bufs = [
+                IoSlice::new(b"hello "),
+                IoSlice::new(b""),
+                IoSlice::new(b"world"),
+            ];
+
+            client.write_all_vectored(&mut bufs).await.unwrap();
+
+            let mut first = [0; 4];
+            let mut second = [0; 7];
+            let mut total = 0;
+
+            while total < 11 {
+                let mut bufs = [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)];
+
+                let mut remaining = &mut bufs[..];
+
+                IoSliceMut::advance_slices(&mut remaining, total);
+                total += server.read_vectored(remaining).await.unwrap();
+            }
+
+            assert_eq!(&first, b"hell");
+            assert_eq!(&second, b"o world");
+        });
+    }
+
+    #[test]
+    fn peek_leaves_the_data_for_read() {
+        block_on(async {
+            let (mut client, mut server) = tcp_pair().await;
+            let mut buffer = [0; 4];
+
+            client.write_all(b"ping").await.unwrap();
+
+            let mut peeked = 0;
+
+            while peeked < 4 {
+                peeked = server.peek(&mut buffer).await.unwrap();
+            }
+
+            assert_eq!(&buffer, b"ping");
+
+            buffer = [0; 4];
+            server.read_exact(&mut buffer).await.unwrap();
+
+            assert_eq!(&buffer, b"ping");
+        });
+    }
+
+    #[test]
+    fn sendfile_sends_the_requested_range() {
+        block_on(async {
+            let path = std::env::temp_dir().join(format!("racing-sendfile-{}", std::process::id()));
+
+            std::fs::write(&path, b"hello world").unwrap();
+
+            let file = File::open(&path).unwrap();
+            let (client, mut server) = tcp_pair().await;
+            let mut received = Vec::new();
+
+            assert_eq!(client.sendfile(&file, 6, 5).await.unwrap(), 5);
+            assert_eq!(client.sendfile(&file, 9, 100).await.unwrap(), 2);
+            drop(client);
+            server.read_to_end(&mut received).await.unwrap();
+            std::fs::remove_file(path).unwrap();
+
+            assert_eq!(received, b"worldld");
+        });
+    }
+
+    #[test]
+    fn try_io_after_readiness() {
+        block_on(async {
+            let (client, server) = tcp_pair().await;
+            let mut buffer = [0; 8];
+
+            assert_eq!(
+                server.try_read(&mut buffer).unwrap_err().kind(),
+                ErrorKind::WouldBlock
+            );
+            assert!(client
+                .ready(Interest::WRITABLE)
+                .await
+                .unwrap()
+                .is_writable());
+            assert_eq!(client.try_write(b"ping").unwrap(), 4);
+
+            server.readable().await.unwrap();
+
+            assert_eq!(server.try_read(&mut buffer).unwrap(), 4);
+            assert_eq!(&buffer[..4], b"ping");
+        });
+    }
+
+    #[test]
+    fn interleave_families_alternates_starting_with_the_first() {
+        let addresses: Vec<SocketAddr> = ["[::1]:1", "[::1]:2", "127.0.0.1:3", "127.0.0.1:4"]
+            .iter()
+            .map(|address| address.parse().unwrap())
+            .collect();
+        let interleaved: Vec<u16> = interleave_families(addresses.into_iter())
+            .iter()
+            .map(SocketAddr::port)
+            .collect();
+
+        assert_eq!(interleaved, [1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn connect_falls_back_to_the_next_address() {
+        block_on(async {
+            let refused = TcpListener::bind("127.0.0.1:0")
+                .unwrap()
+                .local_addr()
+                .unwrap();
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addresses = [refused, listener.local_addr().unwrap()];
+            let started = Instant::now();
+            let stream = TcpStream::connect(&addresses[..]).await.unwrap();
+
+            assert_eq!(stream.peer_addr().unwrap(), addresses[1]);
+            assert!(started.elapsed() < CONNECTION_ATTEMPT_DELAY);
+        });
+    }
 }
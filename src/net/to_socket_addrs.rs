@@ -0,0 +1,110 @@
+use std::{
+    future::Future,
+    io::{Error, ErrorKind, Result},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    vec,
+};
+
+use super::resolver;
+
+pub trait ToSocketAddrs {
+    fn to_socket_addrs(&self) -> impl Future<Output = Result<vec::IntoIter<SocketAddr>>>;
+}
+
+impl ToSocketAddrs for SocketAddr {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        Ok(vec![*self].into_iter())
+    }
+}
+
+impl ToSocketAddrs for SocketAddrV4 {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        Ok(vec![SocketAddr::V4(*self)].into_iter())
+    }
+}
+
+impl ToSocketAddrs for SocketAddrV6 {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        Ok(vec![SocketAddr::V6(*self)].into_iter())
+    }
+}
+
+impl ToSocketAddrs for (IpAddr, u16) {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        Ok(vec![SocketAddr::new(self.0, self.1)].into_iter())
+    }
+}
+
+impl ToSocketAddrs for (Ipv4Addr, u16) {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        Ok(vec![SocketAddr::new(IpAddr::V4(self.0), self.1)].into_iter())
+    }
+}
+
+impl ToSocketAddrs for (Ipv6Addr, u16) {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        Ok(vec![SocketAddr::new(IpAddr::V6(self.0), self.1)].into_iter())
+    }
+}
+
+impl ToSocketAddrs for (&str, u16) {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        resolve(self.0, self.1).await
+    }
+}
+
+impl ToSocketAddrs for (String, u16) {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        resolve(&self.0, self.1).await
+    }
+}
+
+impl ToSocketAddrs for str {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        if let Ok(address) = self.parse::<SocketAddr>() {
+            return Ok(vec![address].into_iter());
+        }
+
+        let Some((host, port)) = self.rsplit_once(':') else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Invalid socket address",
+            ));
+        };
+        let Ok(port) = port.parse::<u16>() else {
+            return Err(Error::new(ErrorKind::InvalidInput, "Invalid port value"));
+        };
+
+        resolve(host, port).await
+    }
+}
+
+impl ToSocketAddrs for String {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        self.as_str().to_socket_addrs().await
+    }
+}
+
+impl ToSocketAddrs for [SocketAddr] {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        let addresses: Vec<SocketAddr> = self.to_vec();
+
+        Ok(addresses.into_iter())
+    }
+}
+
+impl<T: ToSocketAddrs + ?Sized> ToSocketAddrs for &T {
+    async fn to_socket_addrs(&self) -> Result<vec::IntoIter<SocketAddr>> {
+        (**self).to_socket_addrs().await
+    }
+}
+
+async fn resolve(host: &str, port: u16) -> Result<vec::IntoIter<SocketAddr>> {
+    Ok(resolver()
+        .lookup_ip(host)
+        .await?
+        .into_iter()
+        .map(|address| SocketAddr::new(address, port))
+        .collect::<Vec<_>>()
+        .into_iter())
+}
@@ -0,0 +1,34 @@
+use std::{io::Result, os::fd::RawFd};
+
+use crate::sys::getsockopt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UCred {
+    pid: libc::pid_t,
+    uid: libc::uid_t,
+    gid: libc::gid_t,
+}
+
+impl UCred {
+    pub(super) fn from_fd(fd: RawFd) -> Result<UCred> {
+        let ucred: libc::ucred = getsockopt(fd, libc::SOL_SOCKET, libc::SO_PEERCRED)?;
+
+        Ok(UCred {
+            pid: ucred.pid,
+            uid: ucred.uid,
+            gid: ucred.gid,
+        })
+    }
+
+    pub fn pid(&self) -> Option<libc::pid_t> {
+        (self.pid != 0).then_some(self.pid)
+    }
+
+    pub fn uid(&self) -> libc::uid_t {
+        self.uid
+    }
+
+    pub fn gid(&self) -> libc::gid_t {
+        self.gid
+    }
+}
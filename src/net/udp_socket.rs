@@ -1,13 +1,25 @@
 use std::{
     future::poll_fn,
-    io::{Error, ErrorKind, Result},
+    io::{Error, ErrorKind, IoSlice, IoSliceMut, Result},
+    mem,
     net::{self, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs},
     os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+    ptr,
     task::Poll,
     time::{Duration, Instant},
 };
 
-use crate::net::poll_net;
+use crate::{
+    io::{Interest, Ready},
+    net::{
+        ancillary, poll_net, sockopt, try_io, wait_ready, with_timeout, RecvBuf, RecvMeta, SendMeta,
+    },
+    runtime::driver::{driver, Driver, READABLE, WRITABLE},
+    sys::{cvt, socket_addr_from_raw, socket_addr_to_raw},
+};
+
+const MAX_BATCH: usize = 1024;
+const CONTROL_LENGTH: usize = 8;
 
 pub struct UdpSocket(net::UdpSocket);
 
@@ -23,23 +35,210 @@ impl UdpSocket {
     }
 
     pub async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
-        poll_net!(self.0, self.read_timeout(), UdpSocket::recv_from(buf))
+        with_timeout(
+            self.read_timeout(),
+            "UdpSocket",
+            driver().recv_from(self.as_raw_fd(), buf),
+        )
+        .await
     }
 
     pub async fn peek_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
         poll_net!(self.0, self.read_timeout(), UdpSocket::peek_from(buf))
     }
 
-    pub async fn send_to<A: ToSocketAddrs>(&self, buf: &[u8], addr: A) -> Result<usize> {
-        let addrs: Vec<SocketAddr> = addr.to_socket_addrs()?.collect();
+    pub async fn send_to<A: crate::net::ToSocketAddrs>(
+        &self,
+        buf: &[u8],
+        addr: A,
+    ) -> Result<usize> {
+        let Some(addr) = addr.to_socket_addrs().await?.next() else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No SocketAddr provided",
+            ));
+        };
 
-        poll_net!(
-            self.0,
+        with_timeout(
             self.write_timeout(),
-            UdpSocket::send_to(buf, &addrs[..])
+            "UdpSocket",
+            driver().send_to(self.as_raw_fd(), buf, &addr),
+        )
+        .await
+    }
+
+    pub async fn recv_many(&self, bufs: &mut [RecvBuf]) -> Result<usize> {
+        let fd = self.as_raw_fd();
+        let mut batch = Batch::for_recv(bufs);
+        let received = with_timeout(
+            self.read_timeout(),
+            "UdpSocket",
+            driver().io(fd, READABLE, || batch.recv(fd)),
+        )
+        .await?;
+
+        for (index, buf) in bufs.iter_mut().enumerate() {
+            buf.clear();
+
+            if index < received {
+                batch.fill(index, buf);
+            }
+        }
+
+        Ok(received)
+    }
+
+    pub async fn send_many(&self, msgs: &[(&[u8], SocketAddr)]) -> Result<usize> {
+        let fd = self.as_raw_fd();
+        let mut batch = Batch::for_send(msgs);
+
+        with_timeout(
+            self.write_timeout(),
+            "UdpSocket",
+            driver().io(fd, WRITABLE, || batch.send(fd)),
+        )
+        .await
+    }
+
+    pub async fn recv_msg(&self, buf: &mut [u8]) -> Result<RecvMeta> {
+        let fd = self.as_raw_fd();
+
+        with_timeout(
+            self.read_timeout(),
+            "UdpSocket",
+            driver().io(fd, READABLE, || ancillary::recv_msg(fd, buf)),
+        )
+        .await
+    }
+
+    pub async fn send_msg(&self, buf: &[u8], addr: SocketAddr, meta: &SendMeta) -> Result<usize> {
+        let fd = self.as_raw_fd();
+        let ipv6 = sockopt::is_ipv6(fd)?;
+        let tos = match meta.ecn() {
+            Some(_) => sockopt::tos(fd)?,
+            None => 0,
+        };
+
+        with_timeout(
+            self.write_timeout(),
+            "UdpSocket",
+            driver().io(fd, WRITABLE, || {
+                ancillary::send_msg(fd, buf, &addr, meta, ipv6, tos)
+            }),
+        )
+        .await
+    }
+
+    pub fn set_recv_pktinfo(&self, pktinfo: bool) -> Result<()> {
+        sockopt::set_family_bool(
+            self.as_raw_fd(),
+            libc::IP_PKTINFO,
+            libc::IPV6_RECVPKTINFO,
+            pktinfo,
+        )
+    }
+
+    pub fn recv_pktinfo(&self) -> Result<bool> {
+        sockopt::family_bool(self.as_raw_fd(), libc::IP_PKTINFO, libc::IPV6_RECVPKTINFO)
+    }
+
+    pub fn set_recv_timestamps(&self, timestamps: bool) -> Result<()> {
+        sockopt::set_bool(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_TIMESTAMPNS,
+            timestamps,
         )
     }
 
+    pub fn recv_timestamps(&self) -> Result<bool> {
+        sockopt::bool(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_TIMESTAMPNS)
+    }
+
+    pub fn set_recv_ttl(&self, ttl: bool) -> Result<()> {
+        sockopt::set_family_bool(
+            self.as_raw_fd(),
+            libc::IP_RECVTTL,
+            libc::IPV6_RECVHOPLIMIT,
+            ttl,
+        )
+    }
+
+    pub fn recv_ttl(&self) -> Result<bool> {
+        sockopt::family_bool(self.as_raw_fd(), libc::IP_RECVTTL, libc::IPV6_RECVHOPLIMIT)
+    }
+
+    pub fn set_recv_tos(&self, tos: bool) -> Result<()> {
+        sockopt::set_family_bool(
+            self.as_raw_fd(),
+            libc::IP_RECVTOS,
+            libc::IPV6_RECVTCLASS,
+            tos,
+        )
+    }
+
+    pub fn recv_tos(&self) -> Result<bool> {
+        sockopt::family_bool(self.as_raw_fd(), libc::IP_RECVTOS, libc::IPV6_RECVTCLASS)
+    }
+
+    pub fn set_gso_segment_size(&self, size: Option<u16>) -> Result<()> {
+        sockopt::set_u32(
+            self.as_raw_fd(),
+            libc::SOL_UDP,
+            libc::UDP_SEGMENT,
+            size.unwrap_or(0) as u32,
+        )
+    }
+
+    pub fn gso_segment_size(&self) -> Result<Option<u16>> {
+        sockopt::u32(self.as_raw_fd(), libc::SOL_UDP, libc::UDP_SEGMENT)
+            .map(|size| (size != 0).then_some(size as u16))
+    }
+
+    pub fn set_gro(&self, gro: bool) -> Result<()> {
+        sockopt::set_bool(self.as_raw_fd(), libc::SOL_UDP, libc::UDP_GRO, gro)
+    }
+
+    pub fn gro(&self) -> Result<bool> {
+        sockopt::bool(self.as_raw_fd(), libc::SOL_UDP, libc::UDP_GRO)
+    }
+
+    pub async fn ready(&self, interest: Interest) -> Result<Ready> {
+        wait_ready(self.as_raw_fd(), interest).await
+    }
+
+    pub async fn readable(&self) -> Result<()> {
+        self.ready(Interest::READABLE).await?;
+
+        Ok(())
+    }
+
+    pub async fn writable(&self) -> Result<()> {
+        self.ready(Interest::WRITABLE).await?;
+
+        Ok(())
+    }
+
+    pub fn try_recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+        try_io(self.as_raw_fd(), Interest::READABLE, || {
+            self.0.recv_from(buf)
+        })
+    }
+
+    pub fn try_recv(&self, buf: &mut [u8]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::READABLE, || self.0.recv(buf))
+    }
+
+    pub fn try_send_to<A: ToSocketAddrs>(&self, buf: &[u8], addr: A) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::WRITABLE, || {
+            self.0.send_to(buf, addr)
+        })
+    }
+
+    pub fn try_send(&self, buf: &[u8]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::WRITABLE, || self.0.send(buf))
+    }
+
     pub fn peer_addr(&self) -> Result<SocketAddr> {
         self.0.peer_addr()
     }
@@ -108,6 +307,30 @@ impl UdpSocket {
         self.0.ttl()
     }
 
+    pub fn set_tos(&self, tos: u32) -> Result<()> {
+        sockopt::set_tos(self.as_raw_fd(), tos)
+    }
+
+    pub fn tos(&self) -> Result<u32> {
+        sockopt::tos(self.as_raw_fd())
+    }
+
+    pub fn set_dscp(&self, dscp: u8) -> Result<()> {
+        sockopt::set_dscp(self.as_raw_fd(), dscp)
+    }
+
+    pub fn dscp(&self) -> Result<u8> {
+        sockopt::dscp(self.as_raw_fd())
+    }
+
+    pub fn set_mark(&self, mark: u32) -> Result<()> {
+        sockopt::set_mark(self.as_raw_fd(), mark)
+    }
+
+    pub fn mark(&self) -> Result<u32> {
+        sockopt::mark(self.as_raw_fd())
+    }
+
     pub fn join_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> Result<()> {
         self.0.join_multicast_v4(multiaddr, interface)
     }
@@ -143,6 +366,200 @@ impl UdpSocket {
     pub async fn peek(&self, buf: &mut [u8]) -> Result<usize> {
         poll_net!(self.0, self.read_timeout(), UdpSocket::peek(buf))
     }
+
+    pub async fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        poll_net!(
+            self,
+            self.write_timeout(),
+            UdpSocket::send_msg_raw(bufs, None)
+        )
+    }
+
+    pub async fn send_to_vectored<A: crate::net::ToSocketAddrs>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        addr: A,
+    ) -> Result<usize> {
+        let Some(addr) = addr.to_socket_addrs().await?.next() else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No SocketAddr provided",
+            ));
+        };
+
+        poll_net!(
+            self,
+            self.write_timeout(),
+            UdpSocket::send_msg_raw(bufs, Some(&addr))
+        )
+    }
+
+    pub async fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        poll_net!(self, self.read_timeout(), UdpSocket::recv_msg_raw(bufs))
+            .map(|(length, _address)| length)
+    }
+
+    pub async fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Result<(usize, SocketAddr)> {
+        poll_net!(self, self.read_timeout(), UdpSocket::recv_msg_raw(bufs))
+    }
+
+    pub fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    fn send_msg_raw(&self, bufs: &[IoSlice<'_>], addr: Option<&SocketAddr>) -> Result<usize> {
+        let mut message: libc::msghdr = unsafe { mem::zeroed() };
+        let address = addr.map(socket_addr_to_raw);
+
+        if let Some((storage, length)) = &address {
+            message.msg_name = storage as *const _ as *mut libc::c_void;
+            message.msg_namelen = *length;
+        }
+
+        message.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+        message.msg_iovlen = bufs.len() as _;
+
+        cvt(unsafe { libc::sendmsg(self.as_raw_fd(), &message, 0) }).map(|length| length as usize)
+    }
+
+    fn recv_msg_raw(&self, bufs: &mut [IoSliceMut<'_>]) -> Result<(usize, SocketAddr)> {
+        let mut message: libc::msghdr = unsafe { mem::zeroed() };
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+
+        message.msg_name = ptr::addr_of_mut!(storage) as *mut libc::c_void;
+        message.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        message.msg_iov = bufs.as_mut_ptr() as *mut libc::iovec;
+        message.msg_iovlen = bufs.len() as _;
+
+        let length = cvt(unsafe { libc::recvmsg(self.as_raw_fd(), &mut message, 0) })?;
+
+        Ok((
+            length as usize,
+            socket_addr_from_raw(&storage, message.msg_namelen)?,
+        ))
+    }
+}
+
+struct Batch {
+    headers: Vec<libc::mmsghdr>,
+    iovecs: Vec<libc::iovec>,
+    storages: Vec<libc::sockaddr_storage>,
+    controls: Vec<[u64; CONTROL_LENGTH]>,
+}
+
+unsafe impl Send for Batch {}
+
+impl Batch {
+    fn for_recv(bufs: &mut [RecvBuf]) -> Batch {
+        let count = bufs.len().min(MAX_BATCH);
+        let mut batch = Batch {
+            headers: Vec::with_capacity(count),
+            iovecs: bufs[..count]
+                .iter_mut()
+                .map(|buf| libc::iovec {
+                    iov_base: buf.buffer.as_mut_ptr() as *mut libc::c_void,
+                    iov_len: buf.buffer.len(),
+                })
+                .collect(),
+            storages: vec![unsafe { mem::zeroed() }; count],
+            controls: vec![[0u64; CONTROL_LENGTH]; count],
+        };
+
+        for index in 0..count {
+            let mut header: libc::mmsghdr = unsafe { mem::zeroed() };
+
+            header.msg_hdr.msg_name = ptr::addr_of_mut!(batch.storages[index]) as *mut libc::c_void;
+            header.msg_hdr.msg_namelen =
+                mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+            header.msg_hdr.msg_iov = &mut batch.iovecs[index];
+            header.msg_hdr.msg_iovlen = 1;
+            header.msg_hdr.msg_control = batch.controls[index].as_mut_ptr() as *mut libc::c_void;
+            header.msg_hdr.msg_controllen = mem::size_of_val(&batch.controls[index]) as _;
+            batch.headers.push(header);
+        }
+
+        batch
+    }
+
+    fn for_send(msgs: &[(&[u8], SocketAddr)]) -> Batch {
+        let count = msgs.len().min(MAX_BATCH);
+        let mut batch = Batch {
+            headers: Vec::with_capacity(count),
+            iovecs: msgs[..count]
+                .iter()
+                .map(|(buf, _)| libc::iovec {
+                    iov_base: buf.as_ptr() as *mut libc::c_void,
+                    iov_len: buf.len(),
+                })
+                .collect(),
+            storages: Vec::with_capacity(count),
+            controls: Vec::new(),
+        };
+
+        for (index, (_, addr)) in msgs[..count].iter().enumerate() {
+            let (storage, length) = socket_addr_to_raw(addr);
+            let mut header: libc::mmsghdr = unsafe { mem::zeroed() };
+
+            batch.storages.push(storage);
+            header.msg_hdr.msg_name = ptr::addr_of_mut!(batch.storages[index]) as *mut libc::c_void;
+            header.msg_hdr.msg_namelen = length;
+            header.msg_hdr.msg_iov = &mut batch.iovecs[index];
+            header.msg_hdr.msg_iovlen = 1;
+            batch.headers.push(header);
+        }
+
+        batch
+    }
+
+    fn recv(&mut self, fd: RawFd) -> Result<usize> {
+        cvt(unsafe {
+            libc::recvmmsg(
+                fd,
+                self.headers.as_mut_ptr(),
+                self.headers.len() as libc::c_uint,
+                0,
+                ptr::null_mut(),
+            )
+        })
+        .map(|received| received as usize)
+    }
+
+    fn send(&mut self, fd: RawFd) -> Result<usize> {
+        cvt(unsafe {
+            libc::sendmmsg(
+                fd,
+                self.headers.as_mut_ptr(),
+                self.headers.len() as libc::c_uint,
+                libc::MSG_NOSIGNAL,
+            )
+        })
+        .map(|sent| sent as usize)
+    }
+
+    fn fill(&self, index: usize, buf: &mut RecvBuf) {
+        let header = &self.headers[index];
+
+        buf.length = header.msg_len as usize;
+        buf.addr = socket_addr_from_raw(&self.storages[index], header.msg_hdr.msg_namelen).ok();
+        buf.truncated = header.msg_hdr.msg_flags & libc::MSG_TRUNC != 0;
+
+        unsafe {
+            let mut control = libc::CMSG_FIRSTHDR(&header.msg_hdr);
+
+            while !control.is_null() {
+                if (*control).cmsg_level == libc::SOL_UDP && (*control).cmsg_type == libc::UDP_GRO {
+                    buf.segment_size = Some(ptr::read_unaligned(
+                        libc::CMSG_DATA(control) as *const libc::c_int
+                    ) as usize);
+                }
+
+                control = libc::CMSG_NXTHDR(&header.msg_hdr, control);
+            }
+        }
+    }
 }
 
 impl AsFd for UdpSocket {
@@ -165,7 +582,7 @@ impl From<OwnedFd> for UdpSocket {
 
 impl From<UdpSocket> for OwnedFd {
     fn from(value: UdpSocket) -> Self {
-        OwnedFd::from(value.0)
+        unsafe { OwnedFd::from_raw_fd(value.into_raw_fd()) }
     }
 }
 
@@ -177,6 +594,221 @@ impl FromRawFd for UdpSocket {
 
 impl IntoRawFd for UdpSocket {
     fn into_raw_fd(self) -> RawFd {
-        self.0.into_raw_fd()
+        let fd = self.as_raw_fd();
+
+        driver().deregister(fd).ok();
+        mem::forget(self);
+
+        fd
+    }
+}
+
+impl Drop for UdpSocket {
+    fn drop(&mut self) {
+        driver().deregister(self.as_raw_fd()).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::*;
+    use crate::{
+        test_util::block_on,
+        thread::{sleep, spawn},
+    };
+
+    #[test]
+    fn vectored_datagrams_keep_boundaries() {
+        block_on(async {
+            let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let bufs = [IoSlice::new(b"head"), IoSlice::new(b"-tail")];
+
+            let sent = sender
+                .send_to_vectored(&bufs, receiver.local_addr().unwrap())
+                .await
+                .unwrap();
+
+            let mut head = [0; 4];
+            let mut tail = [0; 16];
+            let (length, address) = receiver
+                .recv_from_vectored(&mut [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)])
+                .await
+                .unwrap();
+
+            assert_eq!((sent, length), (9, 9));
+            assert_eq!(address, sender.local_addr().unwrap());
+            assert_eq!(&head, b"head");
+            assert_eq!(&tail[..5], b"-tail");
+        });
+    }
+
+    #[test]
+    fn send_to_resolves_host_names() {
+        block_on(async {
+            let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let target = ("localhost", receiver.local_addr().unwrap().port());
+            let mut buffer = [0; 8];
+
+            sender.send_to(b"one", target).await.unwrap();
+            sender
+                .send_to_vectored(&[IoSlice::new(b"tw"), IoSlice::new(b"o")], target)
+                .await
+                .unwrap();
+
+            assert_eq!(receiver.recv(&mut buffer).await.unwrap(), 3);
+            assert_eq!(&buffer[..3], b"one");
+            assert_eq!(receiver.recv(&mut buffer).await.unwrap(), 3);
+            assert_eq!(&buffer[..3], b"two");
+        });
+    }
+
+    #[test]
+    fn try_io_after_readiness() {
+        block_on(async {
+            let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let mut buffer = [0; 8];
+
+            assert_eq!(
+                receiver.try_recv_from(&mut buffer).unwrap_err().kind(),
+                ErrorKind::WouldBlock
+            );
+
+            sender.writable().await.unwrap();
+            sender
+                .try_send_to(b"ping", receiver.local_addr().unwrap())
+                .unwrap();
+            receiver.readable().await.unwrap();
+
+            assert_eq!(
+                receiver.try_recv_from(&mut buffer).unwrap(),
+                (4, sender.local_addr().unwrap())
+            );
+            assert_eq!(&buffer[..4], b"ping");
+        });
+    }
+
+    #[test]
+    fn batches_survive_waiting_for_readiness() {
+        block_on(async {
+            let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let address = receiver.local_addr().unwrap();
+            let delayed = spawn(async move {
+                sleep(Duration::from_millis(50)).await;
+
+                let messages = [
+                    (&b"one"[..], address),
+                    (&b"two"[..], address),
+                    (&b"three!"[..], address),
+                ];
+
+                sender.send_many(&messages).await.unwrap()
+            });
+            let mut bufs = [
+                RecvBuf::new(4),
+                RecvBuf::new(4),
+                RecvBuf::new(4),
+                RecvBuf::new(4),
+            ];
+            let mut received = 0;
+
+            while received < 3 {
+                received += receiver.recv_many(&mut bufs[received..]).await.unwrap();
+            }
+
+            assert_eq!(delayed.await, 3);
+            assert_eq!(bufs[0].data(), b"one");
+            assert_eq!(bufs[1].data(), b"two");
+            assert_eq!(bufs[2].data(), b"thre");
+            assert!(bufs[2].is_truncated());
+            assert!(bufs[3].is_empty());
+        });
+    }
+
+    fn recv_tos(socket: &UdpSocket) -> u8 {
+        let mut buffer = [0u8; 16];
+        let mut iovec = libc::iovec {
+            iov_base: buffer.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buffer.len(),
+        };
+        let mut control = [0u64; 8];
+        let mut message: libc::msghdr = unsafe { mem::zeroed() };
+
+        message.msg_iov = &mut iovec;
+        message.msg_iovlen = 1;
+        message.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        message.msg_controllen = mem::size_of_val(&control) as _;
+
+        cvt(unsafe { libc::recvmsg(socket.as_raw_fd(), &mut message, 0) }).unwrap();
+
+        unsafe {
+            let header = libc::CMSG_FIRSTHDR(&message);
+
+            assert!(!header.is_null());
+            assert_eq!((*header).cmsg_type, libc::IP_TOS);
+
+            *libc::CMSG_DATA(header)
+        }
+    }
+
+    #[test]
+    fn send_msg_ecn_keeps_the_dscp() {
+        block_on(async {
+            let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let meta = SendMeta::new().with_ecn(0x01);
+
+            sender.set_dscp(46).unwrap();
+            receiver.set_recv_tos(true).unwrap();
+            sender
+                .send_msg(b"ecn", receiver.local_addr().unwrap(), &meta)
+                .await
+                .unwrap();
+            receiver.readable().await.unwrap();
+
+            assert_eq!(recv_tos(&receiver), 46 << 2 | 0x01);
+        });
+    }
+
+    #[test]
+    fn send_msg_maps_source_addresses_across_families() {
+        block_on(async {
+            let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let destination = match receiver.local_addr().unwrap() {
+                SocketAddr::V4(address) => {
+                    SocketAddr::new(address.ip().to_ipv6_mapped().into(), address.port())
+                }
+                address => address,
+            };
+            let v4 = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let v6_source = SendMeta::new().with_source("::1".parse().unwrap());
+            let error = v4
+                .send_msg(b"x", receiver.local_addr().unwrap(), &v6_source)
+                .await
+                .unwrap_err();
+
+            assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+            let Ok(dual_stack) = UdpSocket::bind("[::]:0") else {
+                return;
+            };
+            let v4_source = SendMeta::new().with_source("127.0.0.2".parse().unwrap());
+            let mut buffer = [0; 8];
+
+            dual_stack
+                .send_msg(b"mapped", destination, &v4_source)
+                .await
+                .unwrap();
+
+            let (length, from) = receiver.recv_from(&mut buffer).await.unwrap();
+
+            assert_eq!(&buffer[..length], b"mapped");
+            assert_eq!(from.ip(), "127.0.0.2".parse::<IpAddr>().unwrap());
+        });
     }
 }
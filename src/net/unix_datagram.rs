@@ -0,0 +1,293 @@
+use std::{
+    future::poll_fn,
+    io::{Error, ErrorKind, Result},
+    mem,
+    net::Shutdown,
+    os::{
+        fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+        unix::net::{self, SocketAddr},
+    },
+    path::Path,
+    task::Poll,
+    time::{Duration, Instant},
+};
+
+use super::ancillary;
+use crate::{
+    io::{Interest, Ready},
+    net::{poll_net, try_io, wait_ready, with_timeout},
+    runtime::driver::{driver, Driver, READABLE, WRITABLE},
+};
+
+#[derive(Debug)]
+pub struct UnixDatagram(net::UnixDatagram);
+
+impl UnixDatagram {
+    pub fn bind<P: AsRef<Path>>(path: P) -> Result<UnixDatagram> {
+        let socket = net::UnixDatagram::bind(path)?;
+
+        if let Err(error) = socket.set_nonblocking(true) {
+            Err(error)
+        } else {
+            Ok(UnixDatagram(socket))
+        }
+    }
+
+    pub fn bind_addr(addr: &SocketAddr) -> Result<UnixDatagram> {
+        let socket = net::UnixDatagram::bind_addr(addr)?;
+
+        if let Err(error) = socket.set_nonblocking(true) {
+            Err(error)
+        } else {
+            Ok(UnixDatagram(socket))
+        }
+    }
+
+    pub fn unbound() -> Result<UnixDatagram> {
+        let socket = net::UnixDatagram::unbound()?;
+
+        if let Err(error) = socket.set_nonblocking(true) {
+            Err(error)
+        } else {
+            Ok(UnixDatagram(socket))
+        }
+    }
+
+    pub fn pair() -> Result<(UnixDatagram, UnixDatagram)> {
+        let (a, b) = net::UnixDatagram::pair()?;
+
+        a.set_nonblocking(true)?;
+        b.set_nonblocking(true)?;
+
+        Ok((UnixDatagram(a), UnixDatagram(b)))
+    }
+
+    pub fn connect<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.0.connect(path)
+    }
+
+    pub fn connect_addr(&self, addr: &SocketAddr) -> Result<()> {
+        self.0.connect_addr(addr)
+    }
+
+    pub async fn send_to<P: AsRef<Path>>(&self, buf: &[u8], path: P) -> Result<usize> {
+        let path = path.as_ref();
+
+        poll_net!(
+            self.0,
+            self.write_timeout(),
+            UnixDatagram::send_to(buf, path)
+        )
+    }
+
+    pub async fn send_to_addr(&self, buf: &[u8], addr: &SocketAddr) -> Result<usize> {
+        poll_net!(
+            self.0,
+            self.write_timeout(),
+            UnixDatagram::send_to_addr(buf, addr)
+        )
+    }
+
+    pub async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+        poll_net!(self.0, self.read_timeout(), UnixDatagram::recv_from(buf))
+    }
+
+    pub async fn send(&self, buf: &[u8]) -> Result<usize> {
+        poll_net!(self.0, self.write_timeout(), UnixDatagram::send(buf))
+    }
+
+    pub async fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+        poll_net!(self.0, self.read_timeout(), UnixDatagram::recv(buf))
+    }
+
+    pub async fn send_with_fds(&self, buf: &[u8], fds: &[BorrowedFd<'_>]) -> Result<usize> {
+        let fd = self.as_raw_fd();
+
+        with_timeout(
+            self.write_timeout(),
+            "UnixDatagram",
+            driver().io(fd, WRITABLE, || ancillary::send_with_fds(fd, buf, fds)),
+        )
+        .await
+    }
+
+    pub async fn recv_with_fds(&self, buf: &mut [u8]) -> Result<(usize, Vec<OwnedFd>)> {
+        let fd = self.as_raw_fd();
+
+        with_timeout(
+            self.read_timeout(),
+            "UnixDatagram",
+            driver().io(fd, READABLE, || ancillary::recv_with_fds(fd, buf)),
+        )
+        .await
+    }
+
+    pub fn peer_addr(&self) -> Result<SocketAddr> {
+        self.0.peer_addr()
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.0.local_addr()
+    }
+
+    pub fn shutdown(&self, how: Shutdown) -> Result<()> {
+        self.0.shutdown(how)
+    }
+
+    pub fn try_clone(&self) -> Result<UnixDatagram> {
+        Ok(UnixDatagram(self.0.try_clone()?))
+    }
+
+    pub fn set_read_timeout(&self, dur: Option<Duration>) -> Result<()> {
+        self.0.set_read_timeout(dur)
+    }
+
+    pub fn set_write_timeout(&self, dur: Option<Duration>) -> Result<()> {
+        self.0.set_write_timeout(dur)
+    }
+
+    pub fn read_timeout(&self) -> Result<Option<Duration>> {
+        self.0.read_timeout()
+    }
+
+    pub fn write_timeout(&self) -> Result<Option<Duration>> {
+        self.0.write_timeout()
+    }
+
+    pub fn take_error(&self) -> Result<Option<Error>> {
+        self.0.take_error()
+    }
+
+    pub async fn ready(&self, interest: Interest) -> Result<Ready> {
+        wait_ready(self.as_raw_fd(), interest).await
+    }
+
+    pub async fn readable(&self) -> Result<()> {
+        self.ready(Interest::READABLE).await?;
+
+        Ok(())
+    }
+
+    pub async fn writable(&self) -> Result<()> {
+        self.ready(Interest::WRITABLE).await?;
+
+        Ok(())
+    }
+
+    pub fn try_recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+        try_io(self.as_raw_fd(), Interest::READABLE, || {
+            self.0.recv_from(buf)
+        })
+    }
+
+    pub fn try_recv(&self, buf: &mut [u8]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::READABLE, || self.0.recv(buf))
+    }
+
+    pub fn try_send_to<P: AsRef<Path>>(&self, buf: &[u8], path: P) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::WRITABLE, || {
+            self.0.send_to(buf, path)
+        })
+    }
+
+    pub fn try_send(&self, buf: &[u8]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::WRITABLE, || self.0.send(buf))
+    }
+}
+
+impl AsFd for UnixDatagram {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl AsRawFd for UnixDatagram {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl From<OwnedFd> for UnixDatagram {
+    fn from(value: OwnedFd) -> Self {
+        UnixDatagram(net::UnixDatagram::from(value))
+    }
+}
+
+impl From<UnixDatagram> for OwnedFd {
+    fn from(value: UnixDatagram) -> Self {
+        unsafe { OwnedFd::from_raw_fd(value.into_raw_fd()) }
+    }
+}
+
+impl FromRawFd for UnixDatagram {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        UnixDatagram(net::UnixDatagram::from_raw_fd(fd))
+    }
+}
+
+impl IntoRawFd for UnixDatagram {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.as_raw_fd();
+
+        driver().deregister(fd).ok();
+        mem::forget(self);
+
+        fd
+    }
+}
+
+impl Drop for UnixDatagram {
+    fn drop(&mut self) {
+        driver().deregister(self.as_raw_fd()).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::File, io::Read};
+
+    use super::*;
+    use crate::test_util::block_on;
+
+    #[test]
+    fn fds_travel_with_the_datagram() {
+        block_on(async {
+            let (sender, receiver) = UnixDatagram::pair().unwrap();
+            let file = File::open("/proc/self/stat").unwrap();
+
+            sender
+                .send_with_fds(b"file", &[file.as_fd()])
+                .await
+                .unwrap();
+
+            let mut buffer = [0; 8];
+            let (length, mut fds) = receiver.recv_with_fds(&mut buffer).await.unwrap();
+            let mut contents = String::new();
+
+            assert_eq!(&buffer[..length], b"file");
+            assert_eq!(fds.len(), 1);
+            File::from(fds.pop().unwrap())
+                .read_to_string(&mut contents)
+                .unwrap();
+            assert!(!contents.is_empty());
+        });
+    }
+
+    #[test]
+    fn truncated_datagrams_are_reported() {
+        block_on(async {
+            let (sender, receiver) = UnixDatagram::pair().unwrap();
+            let file = File::open("/proc/self/stat").unwrap();
+
+            sender
+                .send_with_fds(b"too long", &[file.as_fd()])
+                .await
+                .unwrap();
+
+            let mut buffer = [0; 4];
+            let error = receiver.recv_with_fds(&mut buffer).await.unwrap_err();
+
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+        });
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    io::{Error, Result},
+    mem,
+    os::{
+        fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+        unix::net::{self, SocketAddr},
+    },
+    path::Path,
+};
+
+use super::UnixStream;
+use crate::{
+    runtime::driver::{driver, Driver, READABLE},
+    stream::Stream,
+};
+
+#[derive(Debug)]
+pub struct UnixListener(net::UnixListener);
+
+impl UnixListener {
+    pub fn bind<P: AsRef<Path>>(path: P) -> Result<UnixListener> {
+        let listener = net::UnixListener::bind(path)?;
+
+        if let Err(error) = listener.set_nonblocking(true) {
+            Err(error)
+        } else {
+            Ok(UnixListener(listener))
+        }
+    }
+
+    pub fn bind_addr(addr: &SocketAddr) -> Result<UnixListener> {
+        let listener = net::UnixListener::bind_addr(addr)?;
+
+        if let Err(error) = listener.set_nonblocking(true) {
+            Err(error)
+        } else {
+            Ok(UnixListener(listener))
+        }
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.0.local_addr()
+    }
+
+    pub fn try_clone(&self) -> Result<UnixListener> {
+        Ok(UnixListener(self.0.try_clone()?))
+    }
+
+    pub async fn accept(&self) -> Result<(UnixStream, SocketAddr)> {
+        let (stream, address) = driver()
+            .io(self.as_raw_fd(), READABLE, || self.0.accept())
+            .await?;
+
+        stream.set_nonblocking(true)?;
+
+        Ok((UnixStream(stream), address))
+    }
+
+    pub fn incoming(&self) -> UnixIncoming<'_> {
+        UnixIncoming { listener: self }
+    }
+
+    pub fn take_error(&self) -> Result<Option<Error>> {
+        self.0.take_error()
+    }
+}
+
+#[derive(Debug)]
+pub struct UnixIncoming<'a> {
+    listener: &'a UnixListener,
+}
+
+impl Stream for UnixIncoming<'_> {
+    type Item = Result<UnixStream>;
+
+    async fn next(&mut self) -> Option<Result<UnixStream>> {
+        Some(
+            self.listener
+                .accept()
+                .await
+                .map(|(stream, _address)| stream),
+        )
+    }
+}
+
+impl AsFd for UnixListener {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl AsRawFd for UnixListener {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl From<OwnedFd> for UnixListener {
+    fn from(value: OwnedFd) -> Self {
+        UnixListener(net::UnixListener::from(value))
+    }
+}
+
+impl From<UnixListener> for OwnedFd {
+    fn from(value: UnixListener) -> Self {
+        unsafe { OwnedFd::from_raw_fd(value.into_raw_fd()) }
+    }
+}
+
+impl FromRawFd for UnixListener {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        UnixListener(net::UnixListener::from_raw_fd(fd))
+    }
+}
+
+impl IntoRawFd for UnixListener {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.as_raw_fd();
+
+        driver().deregister(fd).ok();
+        mem::forget(self);
+
+        fd
+    }
+}
+
+impl Drop for UnixListener {
+    fn drop(&mut self) {
+        driver().deregister(self.as_raw_fd()).ok();
+    }
+}
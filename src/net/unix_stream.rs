@@ -0,0 +1,347 @@
+use std::{
+    future::poll_fn,
+    io::{Error, ErrorKind, IoSlice, IoSliceMut, Read, Result, Write},
+    mem,
+    net::Shutdown,
+    os::{
+        fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+        unix::net::{self, SocketAddr},
+    },
+    path::Path,
+    ptr,
+    task::Poll,
+    time::{Duration, Instant},
+};
+
+use super::{ancillary, UCred};
+use crate::{
+    io::{AsyncRead, AsyncWrite, Interest, Ready},
+    net::{poll_net, try_io, wait_ready, with_timeout},
+    runtime::driver::{driver, Driver, READABLE, WRITABLE},
+    sys::{cvt, unix_addr_to_raw},
+    thread::sleep,
+};
+
+const CONNECT_BACKOFF: Duration = Duration::from_millis(1);
+const MAX_CONNECT_BACKOFF: Duration = Duration::from_millis(100);
+
+#[derive(Debug)]
+pub struct UnixStream(pub(crate) net::UnixStream);
+
+impl UnixStream {
+    pub async fn connect<P: AsRef<Path>>(path: P) -> Result<UnixStream> {
+        Self::connect_addr(&SocketAddr::from_pathname(path)?).await
+    }
+
+    pub async fn connect_addr(addr: &SocketAddr) -> Result<UnixStream> {
+        let (storage, length) = unix_addr_to_raw(addr)?;
+        let socket = cvt(unsafe {
+            libc::socket(
+                libc::AF_UNIX,
+                libc::SOCK_STREAM | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                0,
+            )
+        })?;
+        let stream = unsafe { UnixStream::from_raw_fd(socket) };
+
+        let mut backoff = CONNECT_BACKOFF;
+
+        loop {
+            match cvt(unsafe {
+                libc::connect(
+                    socket,
+                    ptr::addr_of!(storage) as *const libc::sockaddr,
+                    length,
+                )
+            }) {
+                Ok(_) => break,
+                Err(error) => match error.raw_os_error() {
+                    Some(libc::EAGAIN) => {
+                        sleep(backoff).await;
+                        backoff = (backoff * 2).min(MAX_CONNECT_BACKOFF);
+                    }
+                    Some(libc::EINTR) => {}
+                    Some(libc::EISCONN) => break,
+                    _ => return Err(error),
+                },
+            }
+        }
+
+        Ok(stream)
+    }
+
+    pub fn pair() -> Result<(UnixStream, UnixStream)> {
+        let (a, b) = net::UnixStream::pair()?;
+
+        a.set_nonblocking(true)?;
+        b.set_nonblocking(true)?;
+
+        Ok((UnixStream(a), UnixStream(b)))
+    }
+
+    pub async fn send_with_fds(&self, buf: &[u8], fds: &[BorrowedFd<'_>]) -> Result<usize> {
+        let fd = self.as_raw_fd();
+
+        with_timeout(
+            self.write_timeout(),
+            "UnixStream",
+            driver().io(fd, WRITABLE, || ancillary::send_with_fds(fd, buf, fds)),
+        )
+        .await
+    }
+
+    pub async fn recv_with_fds(&self, buf: &mut [u8]) -> Result<(usize, Vec<OwnedFd>)> {
+        let fd = self.as_raw_fd();
+
+        with_timeout(
+            self.read_timeout(),
+            "UnixStream",
+            driver().io(fd, READABLE, || ancillary::recv_with_fds(fd, buf)),
+        )
+        .await
+    }
+
+    pub fn peer_addr(&self) -> Result<SocketAddr> {
+        self.0.peer_addr()
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.0.local_addr()
+    }
+
+    pub fn peer_cred(&self) -> Result<UCred> {
+        UCred::from_fd(self.as_raw_fd())
+    }
+
+    pub fn shutdown(&self, how: Shutdown) -> Result<()> {
+        self.0.shutdown(how)
+    }
+
+    pub fn try_clone(&self) -> Result<UnixStream> {
+        Ok(UnixStream(self.0.try_clone()?))
+    }
+
+    pub fn set_read_timeout(&self, dur: Option<Duration>) -> Result<()> {
+        self.0.set_read_timeout(dur)
+    }
+
+    pub fn set_write_timeout(&self, dur: Option<Duration>) -> Result<()> {
+        self.0.set_write_timeout(dur)
+    }
+
+    pub fn read_timeout(&self) -> Result<Option<Duration>> {
+        self.0.read_timeout()
+    }
+
+    pub fn write_timeout(&self) -> Result<Option<Duration>> {
+        self.0.write_timeout()
+    }
+
+    pub fn take_error(&self) -> Result<Option<Error>> {
+        self.0.take_error()
+    }
+
+    pub async fn ready(&self, interest: Interest) -> Result<Ready> {
+        wait_ready(self.as_raw_fd(), interest).await
+    }
+
+    pub async fn readable(&self) -> Result<()> {
+        self.ready(Interest::READABLE).await?;
+
+        Ok(())
+    }
+
+    pub async fn writable(&self) -> Result<()> {
+        self.ready(Interest::WRITABLE).await?;
+
+        Ok(())
+    }
+
+    pub fn try_read(&self, buf: &mut [u8]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::READABLE, || (&self.0).read(buf))
+    }
+
+    pub fn try_read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::READABLE, || {
+            (&self.0).read_vectored(bufs)
+        })
+    }
+
+    pub fn try_write(&self, buf: &[u8]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::WRITABLE, || {
+            (&self.0).write(buf)
+        })
+    }
+
+    pub fn try_write_vectored(&self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        try_io(self.as_raw_fd(), Interest::WRITABLE, || {
+            (&self.0).write_vectored(bufs)
+        })
+    }
+}
+
+impl AsFd for UnixStream {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl AsRawFd for UnixStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl From<OwnedFd> for UnixStream {
+    fn from(value: OwnedFd) -> Self {
+        UnixStream(net::UnixStream::from(value))
+    }
+}
+
+impl From<UnixStream> for OwnedFd {
+    fn from(value: UnixStream) -> Self {
+        unsafe { OwnedFd::from_raw_fd(value.into_raw_fd()) }
+    }
+}
+
+impl FromRawFd for UnixStream {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        UnixStream(net::UnixStream::from_raw_fd(fd))
+    }
+}
+
+impl IntoRawFd for UnixStream {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.as_raw_fd();
+
+        driver().deregister(fd).ok();
+        mem::forget(self);
+
+        fd
+    }
+}
+
+impl Drop for UnixStream {
+    fn drop(&mut self) {
+        driver().deregister(self.as_raw_fd()).ok();
+    }
+}
+
+impl AsyncRead for &UnixStream {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        with_timeout(
+            self.read_timeout(),
+            "UnixStream",
+            driver().recv(self.as_raw_fd(), buf),
+        )
+        .await
+    }
+
+    async fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        poll_net!(
+            (&self.0),
+            self.read_timeout(),
+            UnixStream::read_vectored(bufs)
+        )
+    }
+}
+
+impl AsyncRead for UnixStream {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        (&*self).read(buf).await
+    }
+
+    async fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        (&*self).read_vectored(bufs).await
+    }
+}
+
+impl AsyncWrite for &UnixStream {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        with_timeout(
+            self.write_timeout(),
+            "UnixStream",
+            driver().send(self.as_raw_fd(), buf),
+        )
+        .await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        poll_net!((&self.0), self.write_timeout(), UnixStream::flush())
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        self.flush().await?;
+        self.0.shutdown(Shutdown::Write)
+    }
+
+    async fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        poll_net!(
+            (&self.0),
+            self.write_timeout(),
+            UnixStream::write_vectored(bufs)
+        )
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+}
+
+impl AsyncWrite for UnixStream {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (&*self).write(buf).await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        (&*self).flush().await
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        AsyncWrite::shutdown(&mut &*self).await
+    }
+
+    async fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        (&*self).write_vectored(bufs).await
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        net::UnixListener,
+        test_util::block_on,
+        thread::{sleep, spawn},
+    };
+
+    #[test]
+    fn connect_waits_for_a_full_backlog() {
+        block_on(async {
+            let path = std::env::temp_dir().join(format!("racing-unix-{}", std::process::id()));
+            let listener = UnixListener::bind(&path).unwrap();
+
+            cvt(unsafe { libc::listen(listener.as_raw_fd(), 0) }).unwrap();
+
+            let _queued = UnixStream::connect(&path).await.unwrap();
+            let pending = spawn({
+                let path = path.clone();
+
+                async move { UnixStream::connect(path).await }
+            });
+
+            sleep(Duration::from_millis(50)).await;
+            assert!(!pending.is_finished());
+
+            let (_accepted, _) = listener.accept().await.unwrap();
+            let mut stream = pending.await.unwrap();
+
+            stream.write_all(b"ping").await.unwrap();
+            std::fs::remove_file(path).unwrap();
+        });
+    }
+}
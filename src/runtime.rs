@@ -1,18 +1,83 @@
 use std::{
-    cell::OnceCell,
+    cell::{Cell, OnceCell},
     collections::VecDeque,
     future::Future,
-    pin::pin,
+    pin::{pin, Pin},
     sync::{Arc, Barrier, Condvar, Mutex},
     task::{Context, Poll, Wake},
     thread::{self, sleep},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use crate::{thread::spawn, BoxFuture};
+use driver::{driver, Driver};
+
+pub(crate) mod driver;
+
+const IDLE_INTERVAL: Duration = Duration::from_millis(1);
 
 thread_local! {
-    pub(crate) static FUTURE_QUEUE: OnceCell<FutureQueue> = OnceCell::new();
+    pub(crate) static FUTURE_QUEUE: OnceCell<FutureQueue> = const { OnceCell::new() };
+    static PARK: Cell<Park> = const { Cell::new(Park::Idle) };
+}
+
+// What the leaf futures polled by a task are waiting for. Wakers are no-ops, so
+// pending tasks are polled again every IDLE_INTERVAL, unless all of them are
+// only waiting for a deadline: the executor then parks in the driver until the
+// earliest one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Park {
+    Idle,
+    Until(Instant),
+    Busy,
+}
+
+impl Park {
+    fn and(self, other: Park) -> Park {
+        match (self, other) {
+            (Park::Busy, _) | (_, Park::Busy) => Park::Busy,
+            (Park::Idle, park) | (park, Park::Idle) => park,
+            (Park::Until(deadline), Park::Until(other)) => Park::Until(deadline.min(other)),
+        }
+    }
+
+    fn note(park: Park) {
+        PARK.with(|current| current.set(current.get().and(park)));
+    }
+}
+
+pub(crate) fn pending<T>() -> Poll<T> {
+    Park::note(Park::Busy);
+
+    Poll::Pending
+}
+
+pub(crate) fn pending_until<T>(deadline: Instant) -> Poll<T> {
+    Park::note(Park::Until(deadline));
+
+    Poll::Pending
+}
+
+fn poll_parked<T>(
+    future: Pin<&mut (impl Future<Output = T> + ?Sized)>,
+    context: &mut Context<'_>,
+) -> (Poll<T>, Park) {
+    PARK.with(|park| park.set(Park::Idle));
+
+    let poll = future.poll(context);
+
+    match PARK.with(|park| park.replace(Park::Idle)) {
+        // Pending without saying why: keep polling it.
+        Park::Idle => (poll, Park::Busy),
+        park => (poll, park),
+    }
+}
+
+fn park(park: Park) {
+    match park {
+        Park::Until(deadline) => driver().park(deadline.saturating_duration_since(Instant::now())),
+        _ => sleep(IDLE_INTERVAL),
+    }
 }
 
 #[derive(Clone)]
@@ -50,6 +115,10 @@ impl FutureQueue {
             .push_back(future);
     }
 
+    fn len(&self) -> usize {
+        self.queue.0.lock().expect("Thread is poisoned").len()
+    }
+
     fn get(&self) -> Option<BoxFuture<'static, ()>> {
         self.queue.0.lock().expect("Thread is poisoned").pop_front()
     }
@@ -63,18 +132,21 @@ impl FutureQueue {
             .collect()
     }
 
-    fn wait(&self) {
+    fn wait(&self, park_: Park) {
         let queue = self.queue.0.lock().expect("Thread is poisoned");
 
         match queue.len() {
             0 => {
                 self.queue.1.wait(queue).ok();
             }
-            1 => sleep(Duration::from_millis(1)),
+            1 => {
+                drop(queue);
+                park(park_);
+            }
             _ => {
                 self.queue.1.notify_one();
-
-                sleep(Duration::from_millis(1));
+                drop(queue);
+                sleep(IDLE_INTERVAL);
             }
         }
     }
@@ -142,17 +214,30 @@ impl Runtime {
         let mut context = Context::from_waker(&waker);
 
         loop {
-            if let Poll::Ready(result) = future.as_mut().poll(&mut context) {
+            let (poll, mut park_) = poll_parked(future.as_mut(), &mut context);
+
+            if let Poll::Ready(result) = poll {
                 return result;
             }
 
+            let mut pending = 0;
+
             for mut future in queue.drain() {
-                if let Poll::Pending = future.as_mut().poll(&mut context) {
+                let (poll, task_park) = poll_parked(future.as_mut(), &mut context);
+
+                if poll.is_pending() {
+                    park_ = park_.and(task_park);
+                    pending += 1;
                     queue.send(future);
                 }
             }
 
-            sleep(Duration::from_millis(1));
+            // Tasks spawned during this pass haven't been polled yet.
+            if queue.len() > pending {
+                park_ = Park::Busy;
+            }
+
+            park(park_);
         }
     }
 
@@ -176,13 +261,18 @@ impl Runtime {
                 let mut context = Context::from_waker(&waker);
 
                 loop {
+                    let mut park = Park::Busy;
+
                     if let Some(mut future) = queue.get() {
-                        if let Poll::Pending = future.as_mut().poll(&mut context) {
+                        let (poll, task_park) = poll_parked(future.as_mut(), &mut context);
+
+                        if poll.is_pending() {
+                            park = task_park;
                             queue.send(future);
                         }
                     }
 
-                    queue.wait();
+                    queue.wait(park);
                 }
             });
         }
@@ -208,3 +298,60 @@ impl Runtime {
         result.take().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{future::poll_fn, task::Waker};
+
+    use super::*;
+    use crate::thread::sleep_util;
+
+    #[test]
+    fn only_deadlines_let_the_executor_park() {
+        let mut context = Context::from_waker(Waker::noop());
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let earlier = deadline - Duration::from_secs(30);
+
+        let (poll, park) = poll_parked(pin!(sleep_util(deadline)), &mut context);
+        assert!(poll.is_pending());
+        assert_eq!(park, Park::Until(deadline));
+
+        let (_, park) = poll_parked(
+            pin!(poll_fn(|_context| {
+                let _: Poll<()> = pending_until(deadline);
+                pending_until::<()>(earlier)
+            })),
+            &mut context,
+        );
+        assert_eq!(park, Park::Until(earlier));
+
+        let (_, park) = poll_parked(
+            pin!(poll_fn(|_context| {
+                let _: Poll<()> = pending_until(deadline);
+                pending::<()>()
+            })),
+            &mut context,
+        );
+        assert_eq!(park, Park::Busy);
+
+        let (_, park) = poll_parked(pin!(std::future::pending::<()>()), &mut context);
+        assert_eq!(park, Park::Busy);
+    }
+
+    #[test]
+    fn sleeping_runtime_wakes_up_at_the_deadline() {
+        let start = Instant::now();
+
+        Runtime::current().block_on(crate::thread::sleep(Duration::from_millis(30)));
+
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(30));
+        assert!(elapsed < Duration::from_secs(1));
+
+        let start = Instant::now();
+
+        driver().park(Duration::from_millis(30));
+
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+}
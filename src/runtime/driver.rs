@@ -0,0 +1,99 @@
+use std::{
+    future::Future,
+    io::{ErrorKind, IoSlice, IoSliceMut, Result},
+    net::SocketAddr,
+    os::fd::{OwnedFd, RawFd},
+    sync::OnceLock,
+    time::Duration,
+};
+
+mod epoll;
+#[cfg(feature = "io-uring")]
+mod io_uring;
+
+pub(crate) use epoll::*;
+#[cfg(feature = "io-uring")]
+pub(crate) use io_uring::*;
+
+#[cfg(not(feature = "io-uring"))]
+pub(crate) type DefaultDriver = Epoll;
+#[cfg(feature = "io-uring")]
+pub(crate) type DefaultDriver = IoUring;
+
+pub(crate) const READABLE: u32 = (libc::EPOLLIN | libc::EPOLLRDHUP) as u32;
+pub(crate) const WRITABLE: u32 = libc::EPOLLOUT as u32;
+pub(crate) const PRIORITY: u32 = libc::EPOLLPRI as u32;
+pub(crate) const CLOSED: u32 = (libc::EPOLLERR | libc::EPOLLHUP) as u32;
+
+pub(crate) fn driver() -> &'static DefaultDriver {
+    static DRIVER: OnceLock<DefaultDriver> = OnceLock::new();
+
+    DRIVER.get_or_init(DefaultDriver::new)
+}
+
+pub(crate) trait Driver: Send + Sync + 'static {
+    fn register(&self, fd: RawFd, events: u32) -> Result<bool>;
+
+    fn deregister(&self, fd: RawFd) -> Result<()>;
+
+    fn ready(&self, fd: RawFd, events: u32) -> impl Future<Output = Result<u32>>;
+
+    fn wait_ready(&self, fd: RawFd, events: u32) -> impl Future<Output = Result<u32>>;
+
+    fn clear_ready(&self, fd: RawFd, events: u32);
+
+    fn park(&self, timeout: Duration);
+
+    fn recv(&self, fd: RawFd, buf: &mut [u8]) -> impl Future<Output = Result<usize>>;
+
+    fn peek(&self, fd: RawFd, buf: &mut [u8]) -> impl Future<Output = Result<usize>>;
+
+    fn recv_vectored(
+        &self,
+        fd: RawFd,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> impl Future<Output = Result<usize>>;
+
+    fn send(&self, fd: RawFd, buf: &[u8]) -> impl Future<Output = Result<usize>>;
+
+    fn send_vectored(&self, fd: RawFd, bufs: &[IoSlice<'_>])
+        -> impl Future<Output = Result<usize>>;
+
+    fn accept(&self, fd: RawFd) -> impl Future<Output = Result<(OwnedFd, SocketAddr)>>;
+
+    fn send_to(
+        &self,
+        fd: RawFd,
+        buf: &[u8],
+        addr: &SocketAddr,
+    ) -> impl Future<Output = Result<usize>>;
+
+    fn recv_from(
+        &self,
+        fd: RawFd,
+        buf: &mut [u8],
+    ) -> impl Future<Output = Result<(usize, SocketAddr)>>;
+
+    fn io<T>(
+        &self,
+        fd: RawFd,
+        events: u32,
+        mut f: impl FnMut() -> Result<T>,
+    ) -> impl Future<Output = Result<T>> {
+        async move {
+            loop {
+                match f() {
+                    Err(error)
+                        if matches!(
+                            error.kind(),
+                            ErrorKind::WouldBlock | ErrorKind::Interrupted
+                        ) => {}
+                    result => return result,
+                }
+
+                self.clear_ready(fd, events);
+                self.ready(fd, events).await?;
+            }
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    future::poll_fn,
+    io::{ErrorKind, IoSlice, IoSliceMut, Result},
+    mem,
+    net::SocketAddr,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+    ptr,
+    sync::{Mutex, TryLockError},
+    task::Poll,
+    thread,
+    time::Duration,
+};
+
+use super::{Driver, CLOSED, PRIORITY, READABLE, WRITABLE};
+use crate::{
+    runtime::pending,
+    sys::{cvt, socket_addr_from_raw, socket_addr_to_raw},
+};
+
+const MAX_EVENTS: usize = 1024;
+
+pub(crate) struct Epoll {
+    epoll: OwnedFd,
+    events: Mutex<Vec<libc::epoll_event>>,
+    readiness: Mutex<HashMap<RawFd, u32>>,
+}
+
+impl Epoll {
+    pub(crate) fn new() -> Epoll {
+        let epoll = cvt(unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) })
+            .expect("Can't create epoll instance");
+
+        Epoll {
+            epoll: unsafe { OwnedFd::from_raw_fd(epoll) },
+            events: Mutex::new(Vec::with_capacity(MAX_EVENTS)),
+            readiness: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_tracked(&self, fd: RawFd) -> bool {
+        self.readiness
+            .lock()
+            .expect("Epoll readiness is poisoned")
+            .contains_key(&fd)
+    }
+
+    fn turn(&self, timeout: Duration) -> Result<()> {
+        let mut events = match self.events.try_lock() {
+            Ok(events) => events,
+            // Another thread is already waiting on the epoll instance.
+            Err(TryLockError::WouldBlock) => {
+                thread::sleep(timeout);
+
+                return Ok(());
+            }
+            Err(TryLockError::Poisoned(_)) => panic!("Epoll events are poisoned"),
+        };
+
+        // Rounded up, so a deadline is not woken for early and spun on.
+        let timeout = timeout
+            .as_nanos()
+            .div_ceil(1_000_000)
+            .min(libc::c_int::MAX as u128) as libc::c_int;
+        let length = loop {
+            match cvt(unsafe {
+                libc::epoll_wait(
+                    self.epoll.as_raw_fd(),
+                    events.as_mut_ptr(),
+                    MAX_EVENTS as libc::c_int,
+                    timeout,
+                )
+            }) {
+                Ok(length) => break length as usize,
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        };
+
+        unsafe { events.set_len(length) };
+
+        let mut readiness = self.readiness.lock().expect("Epoll readiness is poisoned");
+
+        for event in events.iter() {
+            *readiness.entry(event.u64 as RawFd).or_default() |= event.events;
+        }
+
+        events.clear();
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Epoll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Epoll").field("epoll", &self.epoll).finish()
+    }
+}
+
+impl Driver for Epoll {
+    fn register(&self, fd: RawFd, events: u32) -> Result<bool> {
+        let mut event = libc::epoll_event {
+            events,
+            u64: fd as u64,
+        };
+
+        match cvt(unsafe {
+            libc::epoll_ctl(self.epoll.as_raw_fd(), libc::EPOLL_CTL_ADD, fd, &mut event)
+        }) {
+            Ok(_) => Ok(true),
+            Err(error) if error.raw_os_error() == Some(libc::EEXIST) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn deregister(&self, fd: RawFd) -> Result<()> {
+        self.readiness
+            .lock()
+            .expect("Epoll readiness is poisoned")
+            .remove(&fd);
+
+        match cvt(unsafe {
+            libc::epoll_ctl(
+                self.epoll.as_raw_fd(),
+                libc::EPOLL_CTL_DEL,
+                fd,
+                ptr::null_mut(),
+            )
+        }) {
+            Ok(_) => Ok(()),
+            Err(error) if error.raw_os_error() == Some(libc::ENOENT) => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn ready(&self, fd: RawFd, events: u32) -> Result<u32> {
+        match self.register(fd, READABLE | WRITABLE | PRIORITY) {
+            Ok(_) => {}
+            Err(error) if error.raw_os_error() == Some(libc::EPERM) => return Ok(events),
+            Err(error) => return Err(error),
+        }
+
+        self.wait_ready(fd, events).await
+    }
+
+    async fn wait_ready(&self, fd: RawFd, events: u32) -> Result<u32> {
+        poll_fn(|_context| {
+            if let Err(error) = self.turn(Duration::ZERO) {
+                return Poll::Ready(Err(error));
+            }
+
+            let readiness = self
+                .readiness
+                .lock()
+                .expect("Epoll readiness is poisoned")
+                .get(&fd)
+                .copied()
+                .unwrap_or(0);
+
+            if readiness & (events | CLOSED) != 0 {
+                Poll::Ready(Ok(readiness & (events | CLOSED)))
+            } else {
+                pending()
+            }
+        })
+        .await
+    }
+
+    fn clear_ready(&self, fd: RawFd, events: u32) {
+        if let Some(readiness) = self
+            .readiness
+            .lock()
+            .expect("Epoll readiness is poisoned")
+            .get_mut(&fd)
+        {
+            *readiness &= !(events | CLOSED);
+        }
+    }
+
+    fn park(&self, timeout: Duration) {
+        if self.turn(timeout).is_err() {
+            thread::sleep(timeout);
+        }
+    }
+
+    async fn recv(&self, fd: RawFd, buf: &mut [u8]) -> Result<usize> {
+        self.io(fd, READABLE, || {
+            cvt(unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) })
+                .map(|length| length as usize)
+        })
+        .await
+    }
+
+    async fn peek(&self, fd: RawFd, buf: &mut [u8]) -> Result<usize> {
+        self.io(fd, READABLE, || {
+            cvt(unsafe {
+                libc::recv(
+                    fd,
+                    buf.as_mut_ptr() as *mut libc::c_void,
+                    buf.len(),
+                    libc::MSG_PEEK,
+                )
+            })
+            .map(|length| length as usize)
+        })
+        .await
+    }
+
+    async fn recv_vectored(&self, fd: RawFd, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        self.io(fd, READABLE, || {
+            cvt(unsafe {
+                libc::readv(
+                    fd,
+                    bufs.as_mut_ptr() as *const libc::iovec,
+                    bufs.len().min(libc::c_int::MAX as usize) as libc::c_int,
+                )
+            })
+            .map(|length| length as usize)
+        })
+        .await
+    }
+
+    async fn send(&self, fd: RawFd, buf: &[u8]) -> Result<usize> {
+        self.io(fd, WRITABLE, || {
+            cvt(unsafe {
+                libc::send(
+                    fd,
+                    buf.as_ptr() as *const libc::c_void,
+                    buf.len(),
+                    libc::MSG_NOSIGNAL,
+                )
+            })
+            .map(|length| length as usize)
+        })
+        .await
+    }
+
+    async fn send_vectored(&self, fd: RawFd, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        self.io(fd, WRITABLE, || {
+            let mut message: libc::msghdr = unsafe { mem::zeroed() };
+
+            message.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+            message.msg_iovlen = bufs.len();
+
+            cvt(unsafe { libc::sendmsg(fd, &message, libc::MSG_NOSIGNAL) })
+                .map(|length| length as usize)
+        })
+        .await
+    }
+
+    async fn accept(&self, fd: RawFd) -> Result<(OwnedFd, SocketAddr)> {
+        self.io(fd, READABLE, || {
+            let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+            let mut length = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+            let stream = cvt(unsafe {
+                libc::accept4(
+                    fd,
+                    ptr::addr_of_mut!(storage) as *mut libc::sockaddr,
+                    &mut length,
+                    libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                )
+            })?;
+            let stream = unsafe { OwnedFd::from_raw_fd(stream) };
+
+            Ok((stream, socket_addr_from_raw(&storage, length)?))
+        })
+        .await
+    }
+
+    async fn send_to(&self, fd: RawFd, buf: &[u8], addr: &SocketAddr) -> Result<usize> {
+        let (storage, length) = socket_addr_to_raw(addr);
+
+        self.io(fd, WRITABLE, || {
+            cvt(unsafe {
+                libc::sendto(
+                    fd,
+                    buf.as_ptr() as *const libc::c_void,
+                    buf.len(),
+                    libc::MSG_NOSIGNAL,
+                    ptr::addr_of!(storage) as *const libc::sockaddr,
+                    length,
+                )
+            })
+            .map(|length| length as usize)
+        })
+        .await
+    }
+
+    async fn recv_from(&self, fd: RawFd, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+        self.io(fd, READABLE, || {
+            let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+            let mut length = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+            let received = cvt(unsafe {
+                libc::recvfrom(
+                    fd,
+                    buf.as_mut_ptr() as *mut libc::c_void,
+                    buf.len(),
+                    0,
+                    ptr::addr_of_mut!(storage) as *mut libc::sockaddr,
+                    &mut length,
+                )
+            })?;
+
+            Ok((received as usize, socket_addr_from_raw(&storage, length)?))
+        })
+        .await
+    }
+}
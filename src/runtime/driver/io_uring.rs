@@ -0,0 +1,793 @@
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    fmt,
+    future::poll_fn,
+    io::{Error, ErrorKind, IoSlice, IoSliceMut, Result},
+    mem,
+    net::SocketAddr,
+    os::fd::{FromRawFd, OwnedFd, RawFd},
+    ptr,
+    sync::{Mutex, MutexGuard},
+    task::Poll,
+    time::Duration,
+};
+
+use io_uring::{opcode, squeue, types};
+
+use super::{Driver, Epoll, READABLE, WRITABLE};
+use crate::{
+    runtime::pending,
+    sys::{socket_addr_from_raw, socket_addr_to_raw},
+};
+
+const ENTRIES: u32 = 256;
+const CANCEL_USER_DATA: u64 = u64::MAX;
+const MAX_POOLED_BUFFERS: usize = 64;
+
+// What a read consumed from its fd after its future was dropped. The next read
+// on the fd gets it before anything new is submitted.
+enum Leftover {
+    Bytes(Vec<u8>),
+    Datagram(Vec<u8>, SocketAddr),
+    Accepted(OwnedFd, SocketAddr),
+}
+
+type Salvage = fn(i32, Box<dyn Any + Send>) -> Option<Leftover>;
+
+struct Operation {
+    // None once the fd has been deregistered, so nothing is kept for it.
+    fd: Option<RawFd>,
+    result: Option<i32>,
+    resources: Box<dyn Any + Send>,
+    cancelled: bool,
+    salvage: Option<Salvage>,
+}
+
+struct Ring {
+    ring: io_uring::IoUring,
+    operations: HashMap<u64, Operation>,
+    leftovers: HashMap<RawFd, VecDeque<Leftover>>,
+    next_id: u64,
+}
+
+impl Ring {
+    fn push(&mut self, entry: &squeue::Entry) -> Result<bool> {
+        if unsafe { self.ring.submission().push(entry) }.is_err() {
+            self.ring.submit()?;
+
+            if unsafe { self.ring.submission().push(entry) }.is_err() {
+                return Ok(false);
+            }
+        }
+
+        self.ring.submit()?;
+
+        Ok(true)
+    }
+
+    fn reap(&mut self) -> Result<()> {
+        self.ring.submitter().submit_and_wait(0)?;
+
+        let completions: Vec<_> = self
+            .ring
+            .completion()
+            .map(|entry| (entry.user_data(), entry.result()))
+            .filter(|&(id, _)| id != CANCEL_USER_DATA)
+            .collect();
+
+        for (id, result) in completions {
+            match self.operations.get_mut(&id) {
+                Some(operation) if !operation.cancelled => operation.result = Some(result),
+                Some(_) => self.abandon(id, result),
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    // Drops a finished operation nobody waits for anymore, keeping what it read.
+    fn abandon(&mut self, id: u64, result: i32) {
+        let operation = self.operations.remove(&id).expect("Operation missing");
+        let leftover = match operation.salvage {
+            Some(salvage) if result >= 0 => salvage(result, operation.resources),
+            _ => None,
+        };
+
+        if let (Some(fd), Some(leftover)) = (operation.fd, leftover) {
+            self.leftovers.entry(fd).or_default().push_back(leftover);
+        }
+    }
+
+    fn is_settled(&self, fd: RawFd) -> bool {
+        !self.operations.values().any(|operation| {
+            operation.cancelled && operation.salvage.is_some() && operation.fd == Some(fd)
+        })
+    }
+}
+
+struct Submission<'a> {
+    ring: &'a Mutex<Ring>,
+    id: Option<u64>,
+}
+
+impl Drop for Submission<'_> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut ring = lock(self.ring);
+
+        let Some(operation) = ring.operations.get_mut(&id) else {
+            return;
+        };
+
+        match operation.result {
+            Some(result) => ring.abandon(id, result),
+            None => {
+                operation.cancelled = true;
+
+                let entry = opcode::AsyncCancel::new(id)
+                    .build()
+                    .user_data(CANCEL_USER_DATA);
+
+                ring.push(&entry).ok();
+            }
+        }
+    }
+}
+
+struct SendMsg {
+    buffer: Vec<u8>,
+    iovec: libc::iovec,
+    address: libc::sockaddr_storage,
+    message: libc::msghdr,
+}
+
+unsafe impl Send for SendMsg {}
+
+struct RecvMsg {
+    buffer: Vec<u8>,
+    iovec: libc::iovec,
+    address: libc::sockaddr_storage,
+    message: libc::msghdr,
+}
+
+unsafe impl Send for RecvMsg {}
+
+struct AcceptAddress {
+    storage: libc::sockaddr_storage,
+    length: libc::socklen_t,
+}
+
+fn salvage_bytes(length: i32, resources: Box<dyn Any + Send>) -> Option<Leftover> {
+    let mut buffer = *resources.downcast::<Vec<u8>>().ok()?;
+
+    buffer.truncate(length as usize);
+
+    (!buffer.is_empty()).then_some(Leftover::Bytes(buffer))
+}
+
+fn salvage_datagram(length: i32, resources: Box<dyn Any + Send>) -> Option<Leftover> {
+    let mut message = *resources.downcast::<RecvMsg>().ok()?;
+    let address = socket_addr_from_raw(&message.address, message.message.msg_namelen).ok()?;
+
+    message.buffer.truncate(length as usize);
+
+    Some(Leftover::Datagram(message.buffer, address))
+}
+
+fn salvage_accepted(fd: i32, resources: Box<dyn Any + Send>) -> Option<Leftover> {
+    let stream = unsafe { OwnedFd::from_raw_fd(fd) };
+    let address = resources.downcast::<AcceptAddress>().ok()?;
+
+    Some(Leftover::Accepted(
+        stream,
+        socket_addr_from_raw(&address.storage, address.length).ok()?,
+    ))
+}
+
+pub(crate) struct IoUring {
+    ring: Option<Mutex<Ring>>,
+    buffers: Mutex<Vec<Vec<u8>>>,
+    epoll: Epoll,
+}
+
+impl IoUring {
+    pub(crate) fn new() -> IoUring {
+        IoUring {
+            ring: io_uring::IoUring::new(ENTRIES).ok().map(|ring| {
+                Mutex::new(Ring {
+                    ring,
+                    operations: HashMap::new(),
+                    leftovers: HashMap::new(),
+                    next_id: 0,
+                })
+            }),
+            buffers: Mutex::new(Vec::new()),
+            epoll: Epoll::new(),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.ring.is_some()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_tracked(&self, fd: RawFd) -> bool {
+        self.epoll.is_tracked(fd)
+    }
+
+    fn buffer(&self, length: usize) -> Vec<u8> {
+        let mut buffer = self
+            .buffers
+            .lock()
+            .expect("Io uring buffers are poisoned")
+            .pop()
+            .unwrap_or_default();
+
+        buffer.resize(length, 0);
+
+        buffer
+    }
+
+    fn recycle(&self, mut buffer: Vec<u8>) {
+        let mut buffers = self.buffers.lock().expect("Io uring buffers are poisoned");
+
+        if buffers.len() < MAX_POOLED_BUFFERS {
+            buffer.clear();
+            buffers.push(buffer);
+        }
+    }
+
+    // Waits for reads on the fd whose futures were dropped, and takes what the
+    // first of them consumed.
+    async fn leftover(&self, ring: &Mutex<Ring>, fd: RawFd) -> Result<Option<Leftover>> {
+        poll_fn(|_context| {
+            let mut ring = lock(ring);
+
+            if let Err(error) = ring.reap() {
+                return Poll::Ready(Err(error));
+            }
+
+            if !ring.is_settled(fd) {
+                return pending();
+            }
+
+            Poll::Ready(Ok(ring
+                .leftovers
+                .get_mut(&fd)
+                .and_then(VecDeque::pop_front)))
+        })
+        .await
+    }
+
+    fn put_back(&self, ring: &Mutex<Ring>, fd: RawFd, leftover: Leftover) {
+        lock(ring)
+            .leftovers
+            .entry(fd)
+            .or_default()
+            .push_front(leftover);
+    }
+
+    async fn recv_owned(
+        &self,
+        ring: &Mutex<Ring>,
+        fd: RawFd,
+        length: usize,
+        flags: i32,
+    ) -> Result<(usize, Vec<u8>)> {
+        if let Some(Leftover::Bytes(mut bytes)) = self.leftover(ring, fd).await? {
+            let mut buffer = self.buffer(0);
+            let length = length.min(bytes.len());
+
+            buffer.extend_from_slice(&bytes[..length]);
+
+            if flags & libc::MSG_PEEK != 0 {
+                self.put_back(ring, fd, Leftover::Bytes(bytes));
+            } else if length < bytes.len() {
+                bytes.drain(..length);
+                self.put_back(ring, fd, Leftover::Bytes(bytes));
+            } else {
+                self.recycle(bytes);
+            }
+
+            return Ok((length, buffer));
+        }
+
+        let salvage: Option<Salvage> = match flags & libc::MSG_PEEK {
+            0 => Some(salvage_bytes),
+            _ => None,
+        };
+        let (result, buffer) = self
+            .submit_nonblocking(ring, fd, READABLE, salvage, self.buffer(length), |buffer| {
+                opcode::Recv::new(types::Fd(fd), buffer.as_mut_ptr(), buffer.len() as u32)
+                    .flags(flags)
+                    .build()
+            })
+            .await;
+
+        match result {
+            Ok(length) => Ok((length as usize, buffer)),
+            Err(error) => {
+                self.recycle(buffer);
+
+                Err(error)
+            }
+        }
+    }
+
+    async fn send_owned(&self, ring: &Mutex<Ring>, fd: RawFd, buffer: Vec<u8>) -> Result<usize> {
+        let (result, buffer) = self
+            .submit_nonblocking(ring, fd, WRITABLE, None, buffer, |buffer| {
+                opcode::Send::new(types::Fd(fd), buffer.as_ptr(), buffer.len() as u32)
+                    .flags(libc::MSG_NOSIGNAL)
+                    .build()
+            })
+            .await;
+
+        self.recycle(buffer);
+
+        Ok(result? as usize)
+    }
+
+    // Sockets are nonblocking, and kernels that honor that for ring operations
+    // complete them with EAGAIN instead of waiting. Those are resubmitted once
+    // epoll reports the socket ready.
+    async fn submit_nonblocking<R: Send + 'static>(
+        &self,
+        ring: &Mutex<Ring>,
+        fd: RawFd,
+        events: u32,
+        salvage: Option<Salvage>,
+        mut resources: R,
+        build: impl Fn(&mut R) -> squeue::Entry,
+    ) -> (Result<i32>, R) {
+        loop {
+            let (result, returned) = self.submit(ring, fd, salvage, resources, &build).await;
+
+            resources = returned;
+
+            match result {
+                Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                    self.epoll.clear_ready(fd, events);
+
+                    if let Err(error) = self.epoll.ready(fd, events).await {
+                        return (Err(error), resources);
+                    }
+                }
+                result => return (result, resources),
+            }
+        }
+    }
+
+    async fn submit<R: Send + 'static>(
+        &self,
+        ring: &Mutex<Ring>,
+        fd: RawFd,
+        salvage: Option<Salvage>,
+        resources: R,
+        build: impl FnOnce(&mut R) -> squeue::Entry,
+    ) -> (Result<i32>, R) {
+        let mut resources = Some(Box::new(resources));
+        let entry = build(resources.as_mut().expect("Operation resources missing"));
+        let mut submission = Submission { ring, id: None };
+
+        let result = poll_fn(|_context| {
+            let mut ring = lock(ring);
+
+            let id = match submission.id {
+                Some(id) => id,
+                None => {
+                    let id = ring.next_id;
+
+                    match ring.push(&entry.clone().user_data(id)) {
+                        Ok(true) => {
+                            ring.next_id = (id + 1) % CANCEL_USER_DATA;
+                            ring.operations.insert(
+                                id,
+                                Operation {
+                                    fd: Some(fd),
+                                    result: None,
+                                    resources: resources.take().expect("Operation submitted twice"),
+                                    cancelled: false,
+                                    salvage,
+                                },
+                            );
+                            submission.id = Some(id);
+                        }
+                        Ok(false) => return pending(),
+                        Err(error) => return Poll::Ready(Err(error)),
+                    }
+
+                    id
+                }
+            };
+
+            if let Err(error) = ring.reap() {
+                return Poll::Ready(Err(error));
+            }
+
+            match ring
+                .operations
+                .get(&id)
+                .and_then(|operation| operation.result)
+            {
+                Some(result) => {
+                    let operation = ring
+                        .operations
+                        .remove(&id)
+                        .expect("Completed operation missing");
+
+                    submission.id = None;
+
+                    Poll::Ready(Ok((result, operation.resources)))
+                }
+                None => pending(),
+            }
+        })
+        .await;
+
+        match result {
+            Ok((result, resources)) => {
+                let resources = *resources
+                    .downcast::<R>()
+                    .expect("Operation resources type mismatch");
+
+                if result < 0 {
+                    (Err(Error::from_raw_os_error(-result)), resources)
+                } else {
+                    (Ok(result), resources)
+                }
+            }
+            Err(error) => (Err(error), *resources.expect("Operation resources missing")),
+        }
+    }
+}
+
+fn lock(ring: &Mutex<Ring>) -> MutexGuard<'_, Ring> {
+    ring.lock().expect("Io uring is poisoned")
+}
+
+impl fmt::Debug for IoUring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IoUring")
+            .field("enabled", &self.is_enabled())
+            .field("epoll", &self.epoll)
+            .finish()
+    }
+}
+
+impl Driver for IoUring {
+    fn register(&self, fd: RawFd, events: u32) -> Result<bool> {
+        self.epoll.register(fd, events)
+    }
+
+    fn deregister(&self, fd: RawFd) -> Result<()> {
+        if let Some(ring) = &self.ring {
+            let mut ring = lock(ring);
+
+            ring.leftovers.remove(&fd);
+
+            for operation in ring.operations.values_mut() {
+                if operation.fd == Some(fd) {
+                    operation.fd = None;
+                }
+            }
+        }
+
+        self.epoll.deregister(fd)
+    }
+
+    async fn ready(&self, fd: RawFd, events: u32) -> Result<u32> {
+        self.epoll.ready(fd, events).await
+    }
+
+    async fn wait_ready(&self, fd: RawFd, events: u32) -> Result<u32> {
+        self.epoll.wait_ready(fd, events).await
+    }
+
+    fn clear_ready(&self, fd: RawFd, events: u32) {
+        self.epoll.clear_ready(fd, events)
+    }
+
+    fn park(&self, timeout: Duration) {
+        self.epoll.park(timeout)
+    }
+
+    async fn recv(&self, fd: RawFd, buf: &mut [u8]) -> Result<usize> {
+        let Some(ring) = &self.ring else {
+            return self.epoll.recv(fd, buf).await;
+        };
+
+        let (length, buffer) = self.recv_owned(ring, fd, buf.len(), 0).await?;
+
+        buf[..length].copy_from_slice(&buffer[..length]);
+        self.recycle(buffer);
+
+        Ok(length)
+    }
+
+    async fn peek(&self, fd: RawFd, buf: &mut [u8]) -> Result<usize> {
+        let Some(ring) = &self.ring else {
+            return self.epoll.peek(fd, buf).await;
+        };
+
+        let (length, buffer) = self.recv_owned(ring, fd, buf.len(), libc::MSG_PEEK).await?;
+
+        buf[..length].copy_from_slice(&buffer[..length]);
+        self.recycle(buffer);
+
+        Ok(length)
+    }
+
+    async fn recv_vectored(&self, fd: RawFd, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        let Some(ring) = &self.ring else {
+            return self.epoll.recv_vectored(fd, bufs).await;
+        };
+
+        let capacity = bufs.iter().map(|buf| buf.len()).sum();
+        let (length, buffer) = self.recv_owned(ring, fd, capacity, 0).await?;
+        let mut received = &buffer[..length];
+
+        for buf in bufs {
+            let part = received.len().min(buf.len());
+
+            buf[..part].copy_from_slice(&received[..part]);
+            received = &received[part..];
+        }
+
+        self.recycle(buffer);
+
+        Ok(length)
+    }
+
+    async fn send(&self, fd: RawFd, buf: &[u8]) -> Result<usize> {
+        let Some(ring) = &self.ring else {
+            return self.epoll.send(fd, buf).await;
+        };
+
+        let mut buffer = self.buffer(0);
+
+        buffer.extend_from_slice(buf);
+
+        self.send_owned(ring, fd, buffer).await
+    }
+
+    async fn send_vectored(&self, fd: RawFd, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        let Some(ring) = &self.ring else {
+            return self.epoll.send_vectored(fd, bufs).await;
+        };
+
+        let mut buffer = self.buffer(0);
+
+        for buf in bufs {
+            buffer.extend_from_slice(buf);
+        }
+
+        self.send_owned(ring, fd, buffer).await
+    }
+
+    async fn accept(&self, fd: RawFd) -> Result<(OwnedFd, SocketAddr)> {
+        let Some(ring) = &self.ring else {
+            return self.epoll.accept(fd).await;
+        };
+
+        if let Some(Leftover::Accepted(stream, address)) = self.leftover(ring, fd).await? {
+            return Ok((stream, address));
+        }
+
+        let address = AcceptAddress {
+            storage: unsafe { mem::zeroed() },
+            length: mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t,
+        };
+        let (result, address) = self
+            .submit_nonblocking(
+                ring,
+                fd,
+                READABLE,
+                Some(salvage_accepted),
+                address,
+                |address| {
+                    opcode::Accept::new(
+                        types::Fd(fd),
+                        ptr::addr_of_mut!(address.storage) as *mut libc::sockaddr,
+                        &mut address.length,
+                    )
+                    .flags(libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC)
+                    .build()
+                },
+            )
+            .await;
+        let stream = unsafe { OwnedFd::from_raw_fd(result?) };
+
+        Ok((
+            stream,
+            socket_addr_from_raw(&address.storage, address.length)?,
+        ))
+    }
+
+    async fn send_to(&self, fd: RawFd, buf: &[u8], addr: &SocketAddr) -> Result<usize> {
+        let Some(ring) = &self.ring else {
+            return self.epoll.send_to(fd, buf, addr).await;
+        };
+
+        let (address, address_length) = socket_addr_to_raw(addr);
+        let mut buffer = self.buffer(0);
+
+        buffer.extend_from_slice(buf);
+
+        let message = SendMsg {
+            buffer,
+            iovec: unsafe { mem::zeroed() },
+            address,
+            message: unsafe { mem::zeroed() },
+        };
+        let (result, message) = self
+            .submit_nonblocking(ring, fd, WRITABLE, None, message, |message| {
+                message.iovec.iov_base = message.buffer.as_mut_ptr() as *mut libc::c_void;
+                message.iovec.iov_len = message.buffer.len();
+                message.message.msg_name = ptr::addr_of_mut!(message.address) as *mut libc::c_void;
+                message.message.msg_namelen = address_length;
+                message.message.msg_iov = &mut message.iovec;
+                message.message.msg_iovlen = 1;
+
+                opcode::SendMsg::new(types::Fd(fd), &message.message)
+                    .flags(libc::MSG_NOSIGNAL as u32)
+                    .build()
+            })
+            .await;
+
+        self.recycle(message.buffer);
+
+        Ok(result? as usize)
+    }
+
+    async fn recv_from(&self, fd: RawFd, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+        let Some(ring) = &self.ring else {
+            return self.epoll.recv_from(fd, buf).await;
+        };
+
+        if let Some(Leftover::Datagram(datagram, address)) = self.leftover(ring, fd).await? {
+            // Like recvfrom, whatever doesn't fit in buf is dropped.
+            let length = datagram.len().min(buf.len());
+
+            buf[..length].copy_from_slice(&datagram[..length]);
+            self.recycle(datagram);
+
+            return Ok((length, address));
+        }
+
+        let message = RecvMsg {
+            buffer: self.buffer(buf.len()),
+            iovec: unsafe { mem::zeroed() },
+            address: unsafe { mem::zeroed() },
+            message: unsafe { mem::zeroed() },
+        };
+        let (result, message) = self
+            .submit_nonblocking(
+                ring,
+                fd,
+                READABLE,
+                Some(salvage_datagram),
+                message,
+                |message| {
+                    message.iovec.iov_base = message.buffer.as_mut_ptr() as *mut libc::c_void;
+                    message.iovec.iov_len = message.buffer.len();
+                    message.message.msg_name =
+                        ptr::addr_of_mut!(message.address) as *mut libc::c_void;
+                    message.message.msg_namelen =
+                        mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+                    message.message.msg_iov = &mut message.iovec;
+                    message.message.msg_iovlen = 1;
+
+                    opcode::RecvMsg::new(types::Fd(fd), &mut message.message).build()
+                },
+            )
+            .await;
+        let result = result.and_then(|length| {
+            let length = length as usize;
+
+            buf[..length].copy_from_slice(&message.buffer[..length]);
+
+            Ok((
+                length,
+                socket_addr_from_raw(&message.address, message.message.msg_namelen)?,
+            ))
+        });
+
+        self.recycle(message.buffer);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::Future, os::fd::AsRawFd, pin::pin, time::Duration};
+
+    use super::*;
+    use crate::{
+        io::AsyncWrite,
+        test_util::{block_on, tcp_pair},
+        thread::{sleep, spawn},
+    };
+
+    #[test]
+    fn socket_ops_complete_through_the_ring_and_reuse_buffers() {
+        block_on(async {
+            let uring = IoUring::new();
+
+            if !uring.is_enabled() {
+                return;
+            }
+
+            let (mut client, server) = tcp_pair().await;
+            let writer = spawn(async move {
+                sleep(Duration::from_millis(50)).await;
+                client.write_all(b"hello").await.unwrap();
+                client
+            });
+            let mut buffer = [0; 16];
+
+            assert_eq!(
+                uring.recv(server.as_raw_fd(), &mut buffer).await.unwrap(),
+                5
+            );
+            assert_eq!(&buffer[..5], b"hello");
+
+            let client = writer.await;
+
+            assert_eq!(uring.send(server.as_raw_fd(), b"world").await.unwrap(), 5);
+            assert_eq!(
+                uring.recv(client.as_raw_fd(), &mut buffer).await.unwrap(),
+                5
+            );
+            assert_eq!(&buffer[..5], b"world");
+            assert_eq!(uring.buffers.lock().unwrap().len(), 1);
+        });
+    }
+
+    #[test]
+    fn dropped_receives_hand_their_data_to_the_next_one() {
+        block_on(async {
+            let uring = IoUring::new();
+
+            if !uring.is_enabled() {
+                return;
+            }
+
+            let (mut client, server) = tcp_pair().await;
+            let mut buffer = [0; 16];
+
+            {
+                let mut receive = pin!(uring.recv(server.as_raw_fd(), &mut buffer));
+
+                assert!(
+                    poll_fn(|context| Poll::Ready(receive.as_mut().poll(context)))
+                        .await
+                        .is_pending()
+                );
+
+                client.write_all(b"hello").await.unwrap();
+                sleep(Duration::from_millis(20)).await;
+            }
+
+            client.write_all(b" world").await.unwrap();
+
+            let mut received = Vec::new();
+
+            while !received.ends_with(b"world") {
+                let length = uring
+                    .recv(server.as_raw_fd(), &mut buffer[..4])
+                    .await
+                    .unwrap();
+
+                received.extend_from_slice(&buffer[..length]);
+            }
+
+            assert_eq!(received, b"hello world");
+        });
+    }
+}
@@ -0,0 +1,38 @@
+use std::io::Result;
+
+mod registry;
+pub mod unix;
+
+use unix::{signal, SignalKind};
+
+pub async fn ctrl_c() -> Result<()> {
+    signal(SignalKind::interrupt())?.recv().await;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::{poll_fn, Future},
+        pin::pin,
+        task::Poll,
+    };
+
+    use super::*;
+    use crate::test_util::block_on;
+
+    #[test]
+    fn ctrl_c_waits_for_sigint() {
+        block_on(async {
+            let mut ctrl_c = pin!(ctrl_c());
+
+            assert!(
+                poll_fn(|context| Poll::Ready(ctrl_c.as_mut().poll(context).is_pending())).await
+            );
+            assert_eq!(unsafe { libc::kill(libc::getpid(), libc::SIGINT) }, 0);
+
+            ctrl_c.await.unwrap();
+        });
+    }
+}
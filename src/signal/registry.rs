@@ -0,0 +1,143 @@
+use std::{
+    io::{Error, ErrorKind, Result},
+    mem,
+    os::fd::{AsRawFd, OwnedFd, RawFd},
+    ptr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex, OnceLock,
+    },
+};
+
+use crate::{
+    runtime::driver::{driver, Driver, READABLE},
+    sys::{cvt, pipe},
+};
+
+const MAX_SIGNAL: usize = 65;
+const FORBIDDEN: [libc::c_int; 5] = [
+    libc::SIGILL,
+    libc::SIGFPE,
+    libc::SIGKILL,
+    libc::SIGSEGV,
+    libc::SIGSTOP,
+];
+
+struct Slot {
+    installed: AtomicBool,
+    deliveries: AtomicU64,
+}
+
+struct Registry {
+    receiver: OwnedFd,
+    sender: OwnedFd,
+    install: Mutex<()>,
+    slots: [Slot; MAX_SIGNAL],
+}
+
+static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+fn registry() -> &'static Registry {
+    REGISTRY.get_or_init(|| {
+        let (receiver, sender) = pipe().expect("Can't create signal pipe");
+
+        driver()
+            .register(receiver.as_raw_fd(), READABLE)
+            .expect("Can't register signal pipe");
+
+        Registry {
+            receiver,
+            sender,
+            install: Mutex::new(()),
+            slots: [const {
+                Slot {
+                    installed: AtomicBool::new(false),
+                    deliveries: AtomicU64::new(0),
+                }
+            }; MAX_SIGNAL],
+        }
+    })
+}
+
+extern "C" fn handler(signum: libc::c_int) {
+    let Some(registry) = REGISTRY.get() else {
+        return;
+    };
+    let Some(slot) = registry.slots.get(signum as usize) else {
+        return;
+    };
+    let errno = unsafe { *libc::__errno_location() };
+
+    slot.deliveries.fetch_add(1, Ordering::Release);
+
+    unsafe {
+        libc::write(
+            registry.sender.as_raw_fd(),
+            [1u8].as_ptr() as *const libc::c_void,
+            1,
+        );
+        *libc::__errno_location() = errno;
+    }
+}
+
+pub(super) fn install(signum: libc::c_int) -> Result<()> {
+    if signum <= 0 || signum as usize >= MAX_SIGNAL || FORBIDDEN.contains(&signum) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Signal {signum} can't be registered"),
+        ));
+    }
+
+    let registry = registry();
+    let slot = &registry.slots[signum as usize];
+
+    if slot.installed.load(Ordering::Acquire) {
+        return Ok(());
+    }
+
+    let _install = registry
+        .install
+        .lock()
+        .expect("Signal registry is poisoned");
+
+    if slot.installed.load(Ordering::Acquire) {
+        return Ok(());
+    }
+
+    let mut action: libc::sigaction = unsafe { mem::zeroed() };
+
+    action.sa_sigaction = handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    action.sa_flags = libc::SA_RESTART;
+
+    cvt(unsafe { libc::sigemptyset(&mut action.sa_mask) })?;
+    cvt(unsafe { libc::sigaction(signum, &action, ptr::null_mut()) })?;
+
+    slot.installed.store(true, Ordering::Release);
+
+    Ok(())
+}
+
+pub(super) fn deliveries(signum: libc::c_int) -> u64 {
+    registry().slots[signum as usize]
+        .deliveries
+        .load(Ordering::Acquire)
+}
+
+pub(super) fn receiver() -> RawFd {
+    registry().receiver.as_raw_fd()
+}
+
+pub(super) fn drain() {
+    let mut buffer = [0u8; 64];
+
+    while unsafe {
+        libc::read(
+            receiver(),
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            buffer.len(),
+        )
+    } > 0
+    {}
+
+    driver().clear_ready(receiver(), READABLE);
+}
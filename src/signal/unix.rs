@@ -0,0 +1,124 @@
+use std::{
+    future::{poll_fn, Future},
+    io::Result,
+    pin::pin,
+    task::Poll,
+};
+
+use super::registry;
+use crate::{
+    runtime::driver::{driver, Driver, READABLE},
+    stream::Stream,
+};
+
+mod signal_kind;
+
+pub use signal_kind::*;
+
+#[derive(Debug)]
+pub struct Signal {
+    kind: SignalKind,
+    seen: u64,
+}
+
+impl Signal {
+    pub fn kind(&self) -> SignalKind {
+        self.kind
+    }
+
+    pub async fn recv(&mut self) -> Option<()> {
+        let signum = self.kind.as_raw_value();
+
+        loop {
+            let mut ready = pin!(driver().ready(registry::receiver(), READABLE));
+            let delivered = poll_fn(|context| {
+                let deliveries = registry::deliveries(signum);
+
+                if deliveries != self.seen {
+                    self.seen = deliveries;
+
+                    return Poll::Ready(true);
+                }
+
+                ready.as_mut().poll(context).map(|_| false)
+            })
+            .await;
+
+            if delivered {
+                return Some(());
+            }
+
+            registry::drain();
+        }
+    }
+}
+
+impl Stream for Signal {
+    type Item = ();
+
+    async fn next(&mut self) -> Option<()> {
+        self.recv().await
+    }
+}
+
+pub fn signal(kind: SignalKind) -> Result<Signal> {
+    registry::install(kind.as_raw_value())?;
+
+    Ok(Signal {
+        kind,
+        seen: registry::deliveries(kind.as_raw_value()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::*;
+    use crate::test_util::block_on;
+
+    fn raise(kind: SignalKind) {
+        assert_eq!(
+            unsafe { libc::kill(libc::getpid(), kind.as_raw_value()) },
+            0
+        );
+    }
+
+    async fn is_pending(signal: &mut Signal) -> bool {
+        let mut recv = pin!(signal.recv());
+
+        poll_fn(|context| Poll::Ready(recv.as_mut().poll(context).is_pending())).await
+    }
+
+    #[test]
+    fn every_listener_receives_a_delivery() {
+        block_on(async {
+            let mut first = signal(SignalKind::user_defined1()).unwrap();
+            let mut second = signal(SignalKind::user_defined1()).unwrap();
+
+            raise(SignalKind::user_defined1());
+
+            assert_eq!(first.recv().await, Some(()));
+            assert_eq!(second.next().await, Some(()));
+
+            let mut late = signal(SignalKind::user_defined1()).unwrap();
+
+            assert!(is_pending(&mut first).await);
+            assert!(is_pending(&mut late).await);
+
+            raise(SignalKind::user_defined1());
+
+            assert_eq!(late.recv().await, Some(()));
+            assert_eq!(first.recv().await, Some(()));
+        });
+    }
+
+    #[test]
+    fn forbidden_signals_are_rejected() {
+        for signum in [0, libc::SIGKILL, libc::SIGSTOP, libc::SIGSEGV, 65] {
+            let error = signal(SignalKind::from_raw(signum)).unwrap_err();
+
+            assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        }
+    }
+}
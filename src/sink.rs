@@ -0,0 +1,68 @@
+use std::future::Future;
+
+use crate::stream::Stream;
+
+pub mod prelude {
+    pub use super::{Sink, SinkExt};
+}
+
+pub trait Sink<Item> {
+    type Error;
+
+    fn ready(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+
+    fn feed(&mut self, item: Item) -> impl Future<Output = Result<(), Self::Error>>;
+
+    fn flush(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+
+    fn close(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+
+    fn send(&mut self, item: Item) -> impl Future<Output = Result<(), Self::Error>> {
+        async {
+            self.feed(item).await?;
+            self.flush().await
+        }
+    }
+}
+
+impl<Item, S: Sink<Item> + ?Sized> Sink<Item> for &mut S {
+    type Error = S::Error;
+
+    async fn ready(&mut self) -> Result<(), S::Error> {
+        (**self).ready().await
+    }
+
+    async fn feed(&mut self, item: Item) -> Result<(), S::Error> {
+        (**self).feed(item).await
+    }
+
+    async fn flush(&mut self) -> Result<(), S::Error> {
+        (**self).flush().await
+    }
+
+    async fn close(&mut self) -> Result<(), S::Error> {
+        (**self).close().await
+    }
+
+    async fn send(&mut self, item: Item) -> Result<(), S::Error> {
+        (**self).send(item).await
+    }
+}
+
+pub trait SinkExt<Item>: Sink<Item> {
+    fn send_all<S>(&mut self, mut stream: S) -> impl Future<Output = Result<(), Self::Error>>
+    where
+        S: Stream<Item = Item>,
+    {
+        async move {
+            while let Some(item) = stream.next().await {
+                self.ready().await?;
+                self.feed(item).await?;
+            }
+
+            self.flush().await
+        }
+    }
+}
+
+impl<Item, S: Sink<Item> + ?Sized> SinkExt<Item> for S {}
@@ -0,0 +1,140 @@
+use std::future::Future;
+
+mod buffer_unordered;
+mod chunks;
+mod filter;
+mod iter;
+mod map;
+mod merge;
+mod take;
+mod take_while;
+mod then;
+
+pub use buffer_unordered::*;
+pub use chunks::*;
+pub use filter::*;
+pub use iter::*;
+pub use map::*;
+pub use merge::*;
+pub use take::*;
+pub use take_while::*;
+pub use then::*;
+
+pub mod prelude {
+    pub use super::{Stream, StreamExt};
+}
+
+pub trait Stream {
+    type Item;
+
+    // Must be cancel safe: `merge` and `buffer_unordered` drop a pending `next`
+    // whenever another source is ready first, so that must not lose items.
+    fn next(&mut self) -> impl Future<Output = Option<Self::Item>>;
+}
+
+impl<S: Stream + ?Sized> Stream for &mut S {
+    type Item = S::Item;
+
+    async fn next(&mut self) -> Option<S::Item> {
+        (**self).next().await
+    }
+}
+
+pub trait StreamExt: Stream {
+    fn map<T, F>(self, f: F) -> Map<Self, F>
+    where
+        F: FnMut(Self::Item) -> T,
+        Self: Sized,
+    {
+        Map::new(self, f)
+    }
+
+    fn filter<F>(self, predicate: F) -> Filter<Self, F>
+    where
+        F: FnMut(&Self::Item) -> bool,
+        Self: Sized,
+    {
+        Filter::new(self, predicate)
+    }
+
+    fn then<F, Fut>(self, f: F) -> Then<Self, F, Fut>
+    where
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future,
+        Self: Sized,
+    {
+        Then::new(self, f)
+    }
+
+    fn take(self, limit: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, limit)
+    }
+
+    fn take_while<F>(self, predicate: F) -> TakeWhile<Self, F>
+    where
+        F: FnMut(&Self::Item) -> bool,
+        Self: Sized,
+    {
+        TakeWhile::new(self, predicate)
+    }
+
+    fn chunks(self, capacity: usize) -> Chunks<Self>
+    where
+        Self: Sized,
+    {
+        Chunks::new(self, capacity)
+    }
+
+    fn buffer_unordered(self, limit: usize) -> BufferUnordered<Self>
+    where
+        Self::Item: Future,
+        Self: Sized,
+    {
+        BufferUnordered::new(self, limit)
+    }
+
+    fn merge<S>(self, other: S) -> Merge<Self, S>
+    where
+        S: Stream<Item = Self::Item>,
+        Self: Sized,
+    {
+        Merge::new(self, other)
+    }
+
+    fn fold<B, F>(mut self, init: B, mut f: F) -> impl Future<Output = B>
+    where
+        F: FnMut(B, Self::Item) -> B,
+        Self: Sized,
+    {
+        async move {
+            let mut accumulator = init;
+
+            while let Some(item) = self.next().await {
+                accumulator = f(accumulator, item);
+            }
+
+            accumulator
+        }
+    }
+
+    fn collect<C>(mut self) -> impl Future<Output = C>
+    where
+        C: Default + Extend<Self::Item>,
+        Self: Sized,
+    {
+        async move {
+            let mut collection = C::default();
+
+            while let Some(item) = self.next().await {
+                collection.extend(Some(item));
+            }
+
+            collection
+        }
+    }
+}
+
+impl<S: Stream + ?Sized> StreamExt for S {}
@@ -0,0 +1,152 @@
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    task::Poll,
+};
+
+use super::Stream;
+
+pub struct BufferUnordered<S: Stream>
+where
+    S::Item: Future,
+{
+    stream: S,
+    in_flight: Vec<Pin<Box<S::Item>>>,
+    limit: usize,
+    done: bool,
+}
+
+impl<S: Stream> BufferUnordered<S>
+where
+    S::Item: Future,
+{
+    pub(super) fn new(stream: S, limit: usize) -> Self {
+        if limit == 0 {
+            panic!("BufferUnordered limit can't be zero");
+        }
+
+        Self {
+            stream,
+            in_flight: Vec::with_capacity(limit),
+            limit,
+            done: false,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl<S: Stream + fmt::Debug> fmt::Debug for BufferUnordered<S>
+where
+    S::Item: Future,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferUnordered")
+            .field("stream", &self.stream)
+            .field("in_flight", &self.in_flight.len())
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+enum Event<T, O> {
+    Item(Option<T>),
+    Output(O),
+}
+
+impl<S: Stream> Stream for BufferUnordered<S>
+where
+    S::Item: Future,
+{
+    type Item = <S::Item as Future>::Output;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        let Self {
+            stream,
+            in_flight,
+            limit,
+            done,
+        } = self;
+
+        loop {
+            if *done && in_flight.is_empty() {
+                return None;
+            }
+
+            let next = (!*done && in_flight.len() < *limit).then(|| stream.next());
+            let mut next = pin!(next);
+            let event = poll_fn(|context| {
+                for index in 0..in_flight.len() {
+                    if let Poll::Ready(output) = in_flight[index].as_mut().poll(context) {
+                        drop(in_flight.swap_remove(index));
+
+                        return Poll::Ready(Event::Output(output));
+                    }
+                }
+
+                match next.as_mut().as_pin_mut().map(|next| next.poll(context)) {
+                    Some(Poll::Ready(item)) => Poll::Ready(Event::Item(item)),
+                    _ => Poll::Pending,
+                }
+            })
+            .await;
+
+            match event {
+                Event::Output(output) => return Some(output),
+                Event::Item(Some(future)) => in_flight.push(Box::pin(future)),
+                Event::Item(None) => *done = true,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use crate::{
+        stream::{iter, StreamExt},
+        test_util::block_on,
+        thread::sleep,
+    };
+
+    #[test]
+    fn runs_up_to_limit_futures_and_yields_in_completion_order() {
+        block_on(async {
+            let running = Arc::new(AtomicUsize::new(0));
+            let peak = Arc::new(AtomicUsize::new(0));
+            let futures = iter([100, 10, 30, 5]).map(|ms| {
+                let running = running.clone();
+                let peak = peak.clone();
+
+                async move {
+                    peak.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                    sleep(Duration::from_millis(ms)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    ms
+                }
+            });
+            let order: Vec<u64> = futures.buffer_unordered(2).collect().await;
+
+            assert_eq!(order, [10, 30, 5, 100]);
+            assert_eq!(peak.load(Ordering::SeqCst), 2);
+        });
+    }
+}
@@ -0,0 +1,68 @@
+use std::{fmt, mem};
+
+use super::Stream;
+
+pub struct Chunks<S: Stream> {
+    stream: S,
+    items: Vec<S::Item>,
+    capacity: usize,
+    done: bool,
+}
+
+impl<S: Stream> Chunks<S> {
+    pub(super) fn new(stream: S, capacity: usize) -> Self {
+        if capacity == 0 {
+            panic!("Chunks capacity can't be zero");
+        }
+
+        Self {
+            stream,
+            items: Vec::with_capacity(capacity),
+            capacity,
+            done: false,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl<S: Stream + fmt::Debug> fmt::Debug for Chunks<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chunks")
+            .field("stream", &self.stream)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+impl<S: Stream> Stream for Chunks<S> {
+    type Item = Vec<S::Item>;
+
+    async fn next(&mut self) -> Option<Vec<S::Item>> {
+        while !self.done && self.items.len() < self.capacity {
+            match self.stream.next().await {
+                Some(item) => self.items.push(item),
+                None => self.done = true,
+            }
+        }
+
+        if self.items.is_empty() {
+            None
+        } else {
+            Some(mem::replace(
+                &mut self.items,
+                Vec::with_capacity(self.capacity),
+            ))
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+use super::Stream;
+
+pub struct Filter<S, F> {
+    stream: S,
+    predicate: F,
+}
+
+impl<S, F> Filter<S, F> {
+    pub(super) fn new(stream: S, predicate: F) -> Self {
+        Self { stream, predicate }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for Filter<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter")
+            .field("stream", &self.stream)
+            .finish()
+    }
+}
+
+impl<S: Stream, F: FnMut(&S::Item) -> bool> Stream for Filter<S, F> {
+    type Item = S::Item;
+
+    async fn next(&mut self) -> Option<S::Item> {
+        loop {
+            let item = self.stream.next().await?;
+
+            if (self.predicate)(&item) {
+                return Some(item);
+            }
+        }
+    }
+}
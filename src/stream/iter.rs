@@ -0,0 +1,27 @@
+use std::fmt;
+
+use super::Stream;
+
+pub struct Iter<I> {
+    iter: I,
+}
+
+pub fn iter<I: IntoIterator>(iter: I) -> Iter<I::IntoIter> {
+    Iter {
+        iter: iter.into_iter(),
+    }
+}
+
+impl<I: fmt::Debug> fmt::Debug for Iter<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter").field("iter", &self.iter).finish()
+    }
+}
+
+impl<I: Iterator> Stream for Iter<I> {
+    type Item = I::Item;
+
+    async fn next(&mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+}
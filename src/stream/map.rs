@@ -0,0 +1,40 @@
+use std::fmt;
+
+use super::Stream;
+
+pub struct Map<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> Map<S, F> {
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self { stream, f }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for Map<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map").field("stream", &self.stream).finish()
+    }
+}
+
+impl<T, S: Stream, F: FnMut(S::Item) -> T> Stream for Map<S, F> {
+    type Item = T;
+
+    async fn next(&mut self) -> Option<T> {
+        self.stream.next().await.map(&mut self.f)
+    }
+}
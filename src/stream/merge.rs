@@ -0,0 +1,167 @@
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    task::{Context, Poll},
+};
+
+use super::Stream;
+
+pub struct Merge<S, U> {
+    first: S,
+    second: U,
+    first_done: bool,
+    second_done: bool,
+    first_polled_first: bool,
+}
+
+impl<S, U> Merge<S, U> {
+    pub(super) fn new(first: S, second: U) -> Self {
+        Self {
+            first,
+            second,
+            first_done: false,
+            second_done: false,
+            first_polled_first: true,
+        }
+    }
+
+    pub fn into_inner(self) -> (S, U) {
+        (self.first, self.second)
+    }
+
+    pub fn get_ref(&self) -> (&S, &U) {
+        (&self.first, &self.second)
+    }
+
+    pub fn get_mut(&mut self) -> (&mut S, &mut U) {
+        (&mut self.first, &mut self.second)
+    }
+}
+
+impl<S: fmt::Debug, U: fmt::Debug> fmt::Debug for Merge<S, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Merge")
+            .field("first", &self.first)
+            .field("second", &self.second)
+            .finish()
+    }
+}
+
+impl<S, U> Stream for Merge<S, U>
+where
+    S: Stream,
+    U: Stream<Item = S::Item>,
+{
+    type Item = S::Item;
+
+    async fn next(&mut self) -> Option<S::Item> {
+        let Self {
+            first,
+            second,
+            first_done,
+            second_done,
+            first_polled_first,
+        } = self;
+
+        *first_polled_first = !*first_polled_first;
+
+        let mut first = pin!((!*first_done).then(|| first.next()));
+        let mut second = pin!((!*second_done).then(|| second.next()));
+
+        poll_fn(|context| {
+            let item = if *first_polled_first {
+                poll_next(first.as_mut(), first_done, context)
+                    .or_else(|| poll_next(second.as_mut(), second_done, context))
+            } else {
+                poll_next(second.as_mut(), second_done, context)
+                    .or_else(|| poll_next(first.as_mut(), first_done, context))
+            };
+
+            if item.is_some() {
+                return Poll::Ready(item);
+            }
+
+            if *first_done && *second_done {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+fn poll_next<T, F: Future<Output = Option<T>>>(
+    next: Pin<&mut Option<F>>,
+    done: &mut bool,
+    context: &mut Context<'_>,
+) -> Option<T> {
+    if *done {
+        return None;
+    }
+
+    match next.as_pin_mut().map(|next| next.poll(context)) {
+        Some(Poll::Ready(Some(item))) => Some(item),
+        Some(Poll::Ready(None)) => {
+            *done = true;
+
+            None
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        io::{AsyncBufRead, AsyncWrite, BufReader},
+        stream::{iter, StreamExt},
+        test_util::{block_on, tcp_pair},
+        thread::{sleep, spawn},
+    };
+
+    #[test]
+    fn merged_lines_keep_partial_lines() {
+        block_on(async {
+            let (mut slow, slow_server) = tcp_pair().await;
+            let (mut fast, fast_server) = tcp_pair().await;
+            let writers = spawn(async move {
+                slow.write_all(b"al").await.unwrap();
+                fast.write_all(b"one\n").await.unwrap();
+                sleep(Duration::from_millis(20)).await;
+                fast.write_all(b"two\n").await.unwrap();
+                sleep(Duration::from_millis(20)).await;
+                slow.write_all(b"pha\n").await.unwrap();
+            });
+            let lines = BufReader::new(slow_server)
+                .lines()
+                .merge(BufReader::new(fast_server).lines());
+            let mut lines: Vec<String> = lines.take(3).map(Result::unwrap).collect().await;
+
+            writers.await;
+            lines.sort();
+            assert_eq!(lines, ["alpha", "one", "two"]);
+        });
+    }
+
+    #[test]
+    fn merged_then_keeps_pending_futures() {
+        block_on(async {
+            let slow = iter([40, 40]).then(|ms| async move {
+                sleep(Duration::from_millis(ms)).await;
+                ms
+            });
+            let fast = iter([5; 8]).then(|ms| async move {
+                sleep(Duration::from_millis(ms)).await;
+                ms
+            });
+            let items: Vec<u64> = slow.merge(fast).collect().await;
+
+            assert_eq!(items.iter().filter(|ms| **ms == 40).count(), 2);
+            assert_eq!(items.len(), 10);
+        });
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt;
+
+use super::Stream;
+
+pub struct Take<S> {
+    stream: S,
+    remaining: usize,
+}
+
+impl<S> Take<S> {
+    pub(super) fn new(stream: S, limit: usize) -> Self {
+        Self {
+            stream,
+            remaining: limit,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for Take<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Take")
+            .field("stream", &self.stream)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+impl<S: Stream> Stream for Take<S> {
+    type Item = S::Item;
+
+    async fn next(&mut self) -> Option<S::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let item = self.stream.next().await;
+
+        self.remaining = if item.is_some() {
+            self.remaining - 1
+        } else {
+            0
+        };
+
+        item
+    }
+}
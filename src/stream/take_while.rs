@@ -0,0 +1,59 @@
+use std::fmt;
+
+use super::Stream;
+
+pub struct TakeWhile<S, F> {
+    stream: S,
+    predicate: F,
+    done: bool,
+}
+
+impl<S, F> TakeWhile<S, F> {
+    pub(super) fn new(stream: S, predicate: F) -> Self {
+        Self {
+            stream,
+            predicate,
+            done: false,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for TakeWhile<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TakeWhile")
+            .field("stream", &self.stream)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<S: Stream, F: FnMut(&S::Item) -> bool> Stream for TakeWhile<S, F> {
+    type Item = S::Item;
+
+    async fn next(&mut self) -> Option<S::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.stream.next().await {
+            Some(item) if (self.predicate)(&item) => Some(item),
+            _ => {
+                self.done = true;
+
+                None
+            }
+        }
+    }
+}
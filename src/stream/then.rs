@@ -0,0 +1,68 @@
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    pin::Pin,
+};
+
+use super::Stream;
+
+pub struct Then<S, F, Fut> {
+    stream: S,
+    f: F,
+    pending: Option<Pin<Box<Fut>>>,
+}
+
+impl<S, F, Fut> Then<S, F, Fut> {
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            pending: None,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl<S: fmt::Debug, F, Fut> fmt::Debug for Then<S, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Then")
+            .field("stream", &self.stream)
+            .field("pending", &self.pending.is_some())
+            .finish()
+    }
+}
+
+impl<S, F, Fut> Stream for Then<S, F, Fut>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future,
+{
+    type Item = Fut::Output;
+
+    async fn next(&mut self) -> Option<Fut::Output> {
+        if self.pending.is_none() {
+            let item = self.stream.next().await?;
+
+            self.pending = Some(Box::pin((self.f)(item)));
+        }
+
+        let pending = self.pending.as_mut().expect("Then future is missing");
+        let output = poll_fn(|context| pending.as_mut().poll(context)).await;
+
+        self.pending = None;
+
+        Some(output)
+    }
+}
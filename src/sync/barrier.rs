@@ -6,6 +6,7 @@ use std::{
 };
 
 use super::Mutex;
+use crate::runtime::pending;
 
 #[derive(Debug)]
 pub struct BarrierWaitResult(bool);
@@ -55,7 +56,7 @@ impl Barrier {
 
         poll_fn(|context| {
             let Poll::Ready(state_) = state.get_mut().as_mut().poll(context) else {
-                return Poll::Pending;
+                return pending();
             };
 
             state.set(Box::pin(self.state.lock()));
@@ -63,7 +64,7 @@ impl Barrier {
             if state_.generation > generation {
                 Poll::Ready(BarrierWaitResult(leader))
             } else {
-                Poll::Pending
+                pending()
             }
         })
         .await
@@ -8,6 +8,7 @@ use std::{
 };
 
 use super::{Mutex, MutexGuard};
+use crate::runtime::pending;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct WaitTimeoutResult(bool);
@@ -50,7 +51,7 @@ impl Condvar {
                 if !state.queue.contains(&id) {
                     Poll::Ready(())
                 } else {
-                    Poll::Pending
+                    pending()
                 }
             }
         )
@@ -71,7 +72,7 @@ impl Condvar {
                 if !state.queue.contains(&id) && condition(guard) {
                     Poll::Ready(())
                 } else {
-                    Poll::Pending
+                    pending()
                 }
             }
         )
@@ -90,7 +91,7 @@ impl Condvar {
                 if !state.queue.contains(&id) {
                     Poll::Ready(())
                 } else {
-                    Poll::Pending
+                    pending()
                 }
             }
         )
@@ -113,7 +114,7 @@ impl Condvar {
                 if !state.queue.contains(&id) && condition(guard) {
                     Poll::Ready(())
                 } else {
-                    Poll::Pending
+                    pending()
                 }
             }
         )
@@ -159,13 +160,13 @@ macro_rules! poll_wait {
             }
 
             let Poll::Ready(state_) = state.get_mut().as_mut().poll(context) else {
-                return Poll::Pending;
+                return pending();
             };
 
             state.set(Box::pin($state.lock()));
 
             let Poll::Ready(mut guard_) = guard.get_mut().as_mut().poll(context) else {
-                return Poll::Pending;
+                return pending();
             };
 
             let poll_result = $closure(state_, &mut guard_, id);
@@ -196,13 +197,13 @@ macro_rules! poll_wait {
 
         poll_fn(|context| {
             let Poll::Ready(state_) = state.get_mut().as_mut().poll(context) else {
-                return Poll::Pending;
+                return pending();
             };
 
             state.set(Box::pin($state.lock()));
 
             let Poll::Ready(mut guard_) = guard.get_mut().as_mut().poll(context) else {
-                return Poll::Pending;
+                return pending();
             };
 
             let poll_result = $closure(state_, &mut guard_, id);
@@ -11,7 +11,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::sync::Mutex;
+use crate::{runtime::pending, stream::Stream, sync::Mutex};
 
 pub struct Receiver<T> {
     queue: Arc<Mutex<LinkedList<T>>>,
@@ -29,19 +29,17 @@ impl<T> Receiver<T> {
 
         poll_fn(|context| {
             let Poll::Ready(mut queue_) = queue.get_mut().as_mut().poll(context) else {
-                return Poll::Pending;
+                return pending();
             };
 
             queue.set(Box::pin(self.queue.lock()));
 
-            if Arc::strong_count(&self.queue) == 1 {
-                return Poll::Ready(Err(RecvError));
-            }
-
             if let Some(value) = queue_.pop_front() {
                 Poll::Ready(Ok(value))
+            } else if Arc::strong_count(&self.queue) == 1 {
+                Poll::Ready(Err(RecvError))
             } else {
-                Poll::Pending
+                pending()
             }
         })
         .await
@@ -53,43 +51,69 @@ impl<T> Receiver<T> {
 
         poll_fn(|context| {
             let Poll::Ready(mut queue_) = queue.get_mut().as_mut().poll(context) else {
-                return Poll::Pending;
+                return pending();
             };
 
             queue.set(Box::pin(self.queue.lock()));
 
-            if Arc::strong_count(&self.queue) == 1 {
-                return Poll::Ready(Err(RecvTimeoutError::Disconnected));
-            }
-
-            if instant.elapsed() >= timeout {
-                return Poll::Ready(Err(RecvTimeoutError::Timeout));
-            }
-
             if let Some(value) = queue_.pop_front() {
                 Poll::Ready(Ok(value))
+            } else if Arc::strong_count(&self.queue) == 1 {
+                Poll::Ready(Err(RecvTimeoutError::Disconnected))
+            } else if instant.elapsed() >= timeout {
+                Poll::Ready(Err(RecvTimeoutError::Timeout))
             } else {
-                Poll::Pending
+                pending()
             }
         })
         .await
     }
 
     pub async fn try_recv(&self) -> Result<T, TryRecvError> {
-        if Arc::strong_count(&self.queue) == 1 {
-            return Err(TryRecvError::Disconnected);
-        }
-
         if let Some(value) = self.queue.lock().await.pop_front() {
             Ok(value)
+        } else if Arc::strong_count(&self.queue) == 1 {
+            Err(TryRecvError::Disconnected)
         } else {
             Err(TryRecvError::Empty)
         }
     }
 }
 
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    async fn next(&mut self) -> Option<T> {
+        self.recv().await.ok()
+    }
+}
+
 impl<T: fmt::Debug> fmt::Debug for Receiver<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Receiver").finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{runtime::Runtime, sync::mpsc::channel};
+
+    #[test]
+    fn queued_values_are_received_after_disconnect() {
+        Runtime::current().block_on(async {
+            let (sender, receiver) = channel();
+
+            sender.send(1).await.unwrap();
+            sender.send(2).await.unwrap();
+            sender.send(3).await.unwrap();
+            drop(sender);
+
+            assert_eq!(receiver.try_recv().await, Ok(1));
+            assert_eq!(receiver.recv().await, Ok(2));
+            assert_eq!(receiver.recv_timeout(Duration::ZERO).await, Ok(3));
+            assert_eq!(receiver.recv().await, Err(RecvError));
+            assert_eq!(receiver.try_recv().await, Err(TryRecvError::Disconnected));
+        });
+    }
+}
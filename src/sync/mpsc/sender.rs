@@ -4,7 +4,7 @@ use std::{
     sync::{mpsc::SendError, Arc},
 };
 
-use crate::sync::Mutex;
+use crate::{sink::Sink, sync::Mutex};
 
 #[derive(Clone)]
 pub struct Sender<T> {
@@ -33,6 +33,32 @@ impl<T> Sender<T> {
     }
 }
 
+impl<T> Sink<T> for Sender<T> {
+    type Error = SendError<Option<T>>;
+
+    async fn ready(&mut self) -> Result<(), SendError<Option<T>>> {
+        if Arc::strong_count(&self.queue) - Arc::strong_count(&self.sender) == 1 {
+            Ok(())
+        } else {
+            Err(SendError(None))
+        }
+    }
+
+    async fn feed(&mut self, item: T) -> Result<(), SendError<Option<T>>> {
+        Sender::send(self, item)
+            .await
+            .map_err(|SendError(item)| SendError(Some(item)))
+    }
+
+    async fn flush(&mut self) -> Result<(), SendError<Option<T>>> {
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<(), SendError<Option<T>>> {
+        Ok(())
+    }
+}
+
 impl<T: fmt::Debug> fmt::Debug for Sender<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Sender").finish()
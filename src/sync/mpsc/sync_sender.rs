@@ -10,7 +10,7 @@ use std::{
     task::Poll,
 };
 
-use crate::sync::Mutex;
+use crate::{runtime::pending, sink::Sink, sync::Mutex};
 
 #[derive(Clone)]
 pub struct SyncSender<T> {
@@ -32,6 +32,16 @@ impl<T> SyncSender<T> {
     }
 
     pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.push(value).await?;
+
+        if self.bound == 0 {
+            self.wait_taken().await;
+        }
+
+        Ok(())
+    }
+
+    async fn push(&self, value: T) -> Result<(), SendError<T>> {
         let mut queue = Cell::new(Box::pin(self.queue.lock()));
         let mut value = Some(value);
 
@@ -39,14 +49,14 @@ impl<T> SyncSender<T> {
             if let Some(value_) = value.take() {
                 if Arc::strong_count(&self.queue) - Arc::strong_count(&self.sender) == 1 {
                     let Poll::Ready(mut queue_) = queue.get_mut().as_mut().poll(context) else {
-                        return Poll::Pending;
+                        return pending();
                     };
 
                     queue.set(Box::pin(self.queue.lock()));
 
-                    if self.bound >= queue_.len() {
+                    if queue_.len() >= self.capacity() {
                         value = Some(value_);
-                        Poll::Pending
+                        pending()
                     } else {
                         queue_.push_back(value_);
                         Poll::Ready(Ok(()))
@@ -65,7 +75,7 @@ impl<T> SyncSender<T> {
         if Arc::strong_count(&self.queue) - Arc::strong_count(&self.sender) == 1 {
             let mut queue = self.queue.lock().await;
 
-            if self.bound >= queue.len() {
+            if queue.len() >= self.capacity() {
                 Err(TrySendError::Full(value))
             } else {
                 queue.push_back(value);
@@ -75,6 +85,74 @@ impl<T> SyncSender<T> {
             Err(TrySendError::Disconnected(value))
         }
     }
+
+    fn capacity(&self) -> usize {
+        self.bound.max(1)
+    }
+
+    async fn wait_taken(&self) {
+        let mut queue = Cell::new(Box::pin(self.queue.lock()));
+
+        poll_fn(|context| {
+            if Arc::strong_count(&self.queue) - Arc::strong_count(&self.sender) != 1 {
+                return Poll::Ready(());
+            }
+
+            let Poll::Ready(queue_) = queue.get_mut().as_mut().poll(context) else {
+                return pending();
+            };
+
+            queue.set(Box::pin(self.queue.lock()));
+
+            if queue_.is_empty() {
+                Poll::Ready(())
+            } else {
+                pending()
+            }
+        })
+        .await
+    }
+}
+
+impl<T> Sink<T> for SyncSender<T> {
+    type Error = SendError<Option<T>>;
+
+    async fn ready(&mut self) -> Result<(), SendError<Option<T>>> {
+        let mut queue = Cell::new(Box::pin(self.queue.lock()));
+
+        poll_fn(|context| {
+            if Arc::strong_count(&self.queue) - Arc::strong_count(&self.sender) != 1 {
+                return Poll::Ready(Err(SendError(None)));
+            }
+
+            let Poll::Ready(queue_) = queue.get_mut().as_mut().poll(context) else {
+                return pending();
+            };
+
+            queue.set(Box::pin(self.queue.lock()));
+
+            if queue_.len() >= self.capacity() {
+                pending()
+            } else {
+                Poll::Ready(Ok(()))
+            }
+        })
+        .await
+    }
+
+    async fn feed(&mut self, item: T) -> Result<(), SendError<Option<T>>> {
+        SyncSender::send(self, item)
+            .await
+            .map_err(|SendError(item)| SendError(Some(item)))
+    }
+
+    async fn flush(&mut self) -> Result<(), SendError<Option<T>>> {
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<(), SendError<Option<T>>> {
+        Ok(())
+    }
 }
 
 impl<T: fmt::Debug> fmt::Debug for SyncSender<T> {
@@ -82,3 +160,69 @@ impl<T: fmt::Debug> fmt::Debug for SyncSender<T> {
         f.debug_struct("SyncSender").finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{
+        sync::mpsc::sync_channel,
+        test_util::block_on,
+        thread::{sleep, spawn},
+    };
+
+    #[test]
+    fn bounded_channel_holds_bound_values() {
+        block_on(async {
+            let (sender, receiver) = sync_channel(2);
+
+            sender.try_send(1).await.unwrap();
+            sender.send(2).await.unwrap();
+            assert!(matches!(
+                sender.try_send(3).await,
+                Err(TrySendError::Full(3))
+            ));
+            assert_eq!(receiver.recv().await.unwrap(), 1);
+            sender.try_send(3).await.unwrap();
+        });
+    }
+
+    #[test]
+    fn zero_bound_send_waits_for_the_receiver() {
+        block_on(async {
+            let (sender, receiver) = sync_channel(0);
+            let sent = Arc::new(AtomicBool::new(false));
+            let sent_clone = sent.clone();
+            let send = spawn(async move {
+                sender.send("hand-off").await.unwrap();
+                sent_clone.store(true, Ordering::SeqCst);
+            });
+
+            sleep(Duration::from_millis(20)).await;
+            assert!(!sent.load(Ordering::SeqCst));
+            assert_eq!(receiver.recv().await.unwrap(), "hand-off");
+
+            send.await;
+            assert!(sent.load(Ordering::SeqCst));
+        });
+    }
+
+    #[test]
+    fn sink_feeds_the_channel() {
+        block_on(async {
+            let (mut sender, receiver) = sync_channel(1);
+
+            Sink::ready(&mut sender).await.unwrap();
+            Sink::feed(&mut sender, 7).await.unwrap();
+            Sink::flush(&mut sender).await.unwrap();
+            assert_eq!(receiver.recv().await.unwrap(), 7);
+
+            drop(receiver);
+            assert!(Sink::ready(&mut sender).await.is_err());
+        });
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     cell::UnsafeCell,
     fmt,
     future::poll_fn,
+    mem,
     ops::{Deref, DerefMut},
     panic::{RefUnwindSafe, UnwindSafe},
     sync::atomic::{AtomicBool, Ordering},
@@ -9,6 +10,7 @@ use std::{
 };
 
 use super::TryLock;
+use crate::runtime::pending;
 
 pub struct MutexGuard<'a, T> {
     mutex: &'a Mutex<T>,
@@ -18,14 +20,17 @@ unsafe impl<T: Sync> Sync for MutexGuard<'_, T> {}
 
 impl<'a, T> MutexGuard<'a, T> {
     pub(crate) fn unlock(self) -> &'a Mutex<T> {
-        self.mutex.locked.store(false, Ordering::Relaxed);
-        self.mutex
+        let mutex = self.mutex;
+
+        mem::forget(self);
+        mutex.locked.store(false, Ordering::Release);
+        mutex
     }
 }
 
 impl<T> Drop for MutexGuard<'_, T> {
     fn drop(&mut self) {
-        self.mutex.locked.store(false, Ordering::Relaxed);
+        self.mutex.locked.store(false, Ordering::Release);
     }
 }
 
@@ -100,23 +105,29 @@ impl<T> Mutex<T> {
 
     pub async fn lock(&self) -> MutexGuard<'_, T> {
         poll_fn(|_context| {
-            if self.locked.fetch_and(true, Ordering::SeqCst) {
-                Poll::Pending
+            if self.is_locked() {
+                pending()
             } else {
-                Poll::Ready(MutexGuard { mutex: &self })
+                Poll::Ready(MutexGuard { mutex: self })
             }
         })
         .await
     }
 
     pub fn try_lock(&self) -> TryLock<MutexGuard<'_, T>> {
-        if self.locked.fetch_and(true, Ordering::SeqCst) {
+        if self.is_locked() {
             TryLock::WouldBlock
         } else {
-            TryLock::Guard(MutexGuard { mutex: &self })
+            TryLock::Guard(MutexGuard { mutex: self })
         }
     }
 
+    fn is_locked(&self) -> bool {
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+    }
+
     pub fn into_inner(mut self) -> T
     where
         T: Sized,
@@ -146,3 +157,24 @@ impl<T> From<T> for Mutex<T> {
         Mutex::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::block_on;
+
+    #[test]
+    fn lock_excludes_other_lockers() {
+        block_on(async {
+            let mutex = Mutex::new(0);
+            let mut guard = mutex.lock().await;
+
+            assert!(matches!(mutex.try_lock(), TryLock::WouldBlock));
+
+            *guard += 1;
+            drop(guard);
+
+            assert!(matches!(mutex.try_lock(), TryLock::Guard(guard) if *guard == 1));
+        });
+    }
+}
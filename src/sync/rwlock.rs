@@ -9,6 +9,7 @@ use std::{
 };
 
 use super::TryLock;
+use crate::runtime::pending;
 
 pub struct RwLockReadGuard<'a, T> {
     rwlock: &'a RwLock<T>,
@@ -142,28 +143,30 @@ impl<T> RwLock<T> {
 
     pub async fn read(&self) -> RwLockReadGuard<'_, T> {
         poll_fn(|_context| {
-            if let Ok(_) = self
+            if self
                 .locked
                 .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |locked| {
                     (locked > 0).then_some(locked + 1)
                 })
+                .is_ok()
             {
-                Poll::Ready(RwLockReadGuard { rwlock: &self })
+                Poll::Ready(RwLockReadGuard { rwlock: self })
             } else {
-                Poll::Pending
+                pending()
             }
         })
         .await
     }
 
     pub fn try_read(&self) -> TryLock<RwLockReadGuard<'_, T>> {
-        if let Ok(_) = self
+        if self
             .locked
             .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |locked| {
                 (locked > 0).then_some(locked + 1)
             })
+            .is_ok()
         {
-            TryLock::Guard(RwLockReadGuard { rwlock: &self })
+            TryLock::Guard(RwLockReadGuard { rwlock: self })
         } else {
             TryLock::WouldBlock
         }
@@ -171,28 +174,30 @@ impl<T> RwLock<T> {
 
     pub async fn write(&self) -> RwLockWriteGuard<'_, T> {
         poll_fn(|_context| {
-            if let Ok(_) = self
+            if self
                 .locked
                 .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |locked| {
                     (locked == 1).then_some(0)
                 })
+                .is_ok()
             {
-                Poll::Ready(RwLockWriteGuard { rwlock: &self })
+                Poll::Ready(RwLockWriteGuard { rwlock: self })
             } else {
-                Poll::Pending
+                pending()
             }
         })
         .await
     }
 
     pub fn try_write(&self) -> TryLock<RwLockWriteGuard<'_, T>> {
-        if let Ok(_) = self
+        if self
             .locked
             .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |locked| {
                 (locked == 1).then_some(0)
             })
+            .is_ok()
         {
-            TryLock::Guard(RwLockWriteGuard { rwlock: &self })
+            TryLock::Guard(RwLockWriteGuard { rwlock: self })
         } else {
             TryLock::WouldBlock
         }
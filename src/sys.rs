@@ -0,0 +1,187 @@
+use std::{
+    io::{Error, ErrorKind, Result},
+    mem,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    os::fd::{FromRawFd, OwnedFd, RawFd},
+};
+
+pub(crate) trait IsMinusOne {
+    fn is_minus_one(&self) -> bool;
+}
+
+macro_rules! impl_is_minus_one {
+    ($($type:ident)*) => ($(
+        impl IsMinusOne for $type {
+            fn is_minus_one(&self) -> bool {
+                *self == -1
+            }
+        }
+    )*)
+}
+
+impl_is_minus_one! { i32 isize }
+
+pub(crate) fn cvt<T: IsMinusOne>(value: T) -> Result<T> {
+    if value.is_minus_one() {
+        Err(Error::last_os_error())
+    } else {
+        Ok(value)
+    }
+}
+
+pub(crate) fn socket_addr_to_raw(addr: &SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+
+    let length = match addr {
+        SocketAddr::V4(addr) => {
+            let raw = unsafe { &mut *(&mut storage as *mut _ as *mut libc::sockaddr_in) };
+
+            raw.sin_family = libc::AF_INET as libc::sa_family_t;
+            raw.sin_port = addr.port().to_be();
+            raw.sin_addr = libc::in_addr {
+                s_addr: u32::from_ne_bytes(addr.ip().octets()),
+            };
+
+            mem::size_of::<libc::sockaddr_in>()
+        }
+        SocketAddr::V6(addr) => {
+            let raw = unsafe { &mut *(&mut storage as *mut _ as *mut libc::sockaddr_in6) };
+
+            raw.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            raw.sin6_port = addr.port().to_be();
+            raw.sin6_flowinfo = addr.flowinfo();
+            raw.sin6_addr = libc::in6_addr {
+                s6_addr: addr.ip().octets(),
+            };
+            raw.sin6_scope_id = addr.scope_id();
+
+            mem::size_of::<libc::sockaddr_in6>()
+        }
+    };
+
+    (storage, length as libc::socklen_t)
+}
+
+pub(crate) fn socket_addr_from_raw(
+    storage: &libc::sockaddr_storage,
+    length: libc::socklen_t,
+) -> Result<SocketAddr> {
+    match storage.ss_family as libc::c_int {
+        libc::AF_INET if length as usize >= mem::size_of::<libc::sockaddr_in>() => {
+            let raw = unsafe { &*(storage as *const _ as *const libc::sockaddr_in) };
+
+            Ok(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::from(raw.sin_addr.s_addr.to_ne_bytes()),
+                u16::from_be(raw.sin_port),
+            )))
+        }
+        libc::AF_INET6 if length as usize >= mem::size_of::<libc::sockaddr_in6>() => {
+            let raw = unsafe { &*(storage as *const _ as *const libc::sockaddr_in6) };
+
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                Ipv6Addr::from(raw.sin6_addr.s6_addr),
+                u16::from_be(raw.sin6_port),
+                raw.sin6_flowinfo,
+                raw.sin6_scope_id,
+            )))
+        }
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Invalid socket address family",
+        )),
+    }
+}
+
+pub(crate) fn pipe() -> Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+
+    cvt(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) })?;
+
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+pub(crate) fn is_fifo(fd: RawFd) -> Result<bool> {
+    let mut stat: libc::stat = unsafe { mem::zeroed() };
+
+    cvt(unsafe { libc::fstat(fd, &mut stat) })?;
+
+    Ok(stat.st_mode & libc::S_IFMT == libc::S_IFIFO)
+}
+
+pub(crate) fn is_unsupported(error: &Error) -> bool {
+    matches!(
+        error.raw_os_error(),
+        Some(libc::EINVAL | libc::ENOSYS | libc::EOPNOTSUPP | libc::EXDEV)
+    )
+}
+
+pub(crate) fn setsockopt<T>(
+    fd: RawFd,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: T,
+) -> Result<()> {
+    cvt(unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            name,
+            &value as *const T as *const libc::c_void,
+            mem::size_of::<T>() as libc::socklen_t,
+        )
+    })?;
+
+    Ok(())
+}
+
+pub(crate) fn getsockopt<T: Copy>(fd: RawFd, level: libc::c_int, name: libc::c_int) -> Result<T> {
+    let mut value: T = unsafe { mem::zeroed() };
+    let mut length = mem::size_of::<T>() as libc::socklen_t;
+
+    cvt(unsafe {
+        libc::getsockopt(
+            fd,
+            level,
+            name,
+            &mut value as *mut T as *mut libc::c_void,
+            &mut length,
+        )
+    })?;
+
+    Ok(value)
+}
+
+pub(crate) fn unix_addr_to_raw(
+    addr: &std::os::unix::net::SocketAddr,
+) -> Result<(libc::sockaddr_un, libc::socklen_t)> {
+    use std::os::{linux::net::SocketAddrExt, unix::ffi::OsStrExt};
+
+    let mut storage: libc::sockaddr_un = unsafe { mem::zeroed() };
+    let (offset, name) = match (addr.as_pathname(), addr.as_abstract_name()) {
+        (Some(path), _) => (0, path.as_os_str().as_bytes()),
+        (None, Some(name)) => (1, name),
+        (None, None) => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unnamed unix socket address",
+            ))
+        }
+    };
+
+    if offset + name.len() >= storage.sun_path.len() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Unix socket address is too long",
+        ));
+    }
+
+    storage.sun_family = libc::AF_UNIX as libc::sa_family_t;
+
+    for (index, byte) in name.iter().enumerate() {
+        storage.sun_path[offset + index] = *byte as libc::c_char;
+    }
+
+    let length = mem::size_of::<libc::sa_family_t>() + offset + name.len() + (offset == 0) as usize;
+
+    Ok((storage, length as libc::socklen_t))
+}
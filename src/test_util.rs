@@ -0,0 +1,101 @@
+use std::{
+    collections::VecDeque,
+    future::{poll_fn, Future},
+    io::Result,
+    task::Poll,
+};
+
+use crate::{
+    io::{AsyncRead, AsyncWrite},
+    net::{TcpListener, TcpStream},
+    runtime::Runtime,
+};
+
+pub(crate) fn block_on<T: Send + 'static>(future: impl Future<Output = T> + Send + 'static) -> T {
+    Runtime::current().block_on(future)
+}
+
+pub(crate) async fn tcp_pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+
+    (client, server)
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Mock {
+    pub(crate) input: VecDeque<Vec<u8>>,
+    pub(crate) output: Vec<u8>,
+    pub(crate) shutdown: bool,
+    pub(crate) write_limit: Option<usize>,
+    pub(crate) stall_writes: bool,
+    pub(crate) stalled: bool,
+}
+
+impl Mock {
+    pub(crate) fn new<I: IntoIterator<Item = &'static [u8]>>(chunks: I) -> Mock {
+        Mock {
+            input: chunks.into_iter().map(<[u8]>::to_vec).collect(),
+            ..Mock::default()
+        }
+    }
+}
+
+impl AsyncRead for Mock {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let Some(mut chunk) = self.input.pop_front() else {
+            return Ok(0);
+        };
+        let length = chunk.len().min(buf.len());
+
+        buf[..length].copy_from_slice(&chunk[..length]);
+
+        if length < chunk.len() {
+            self.input.push_front(chunk.split_off(length));
+        }
+
+        Ok(length)
+    }
+}
+
+impl AsyncWrite for Mock {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.stall_writes {
+            // Every other write returns Pending once before going through.
+            self.stalled = !self.stalled;
+
+            if self.stalled {
+                let mut polled = false;
+
+                poll_fn(|_context| match polled {
+                    true => Poll::Ready(()),
+                    false => {
+                        polled = true;
+
+                        Poll::Pending
+                    }
+                })
+                .await;
+            }
+        }
+
+        let length = buf.len().min(self.write_limit.unwrap_or(usize::MAX));
+
+        self.output.extend_from_slice(&buf[..length]);
+
+        Ok(length)
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        self.shutdown = true;
+
+        Ok(())
+    }
+}
@@ -1,12 +1,19 @@
 use std::{
     future::{self, poll_fn, Future},
     pin::Pin,
-    sync::{Arc, Mutex},
+    sync::{
+        mpsc::{self, TryRecvError},
+        Arc, Mutex,
+    },
     task::{Context, Poll},
+    thread,
     time::{Duration, Instant},
 };
 
-use crate::{runtime::FutureQueue, BoxFuture};
+use crate::{
+    runtime::{pending, pending_until, FutureQueue},
+    BoxFuture,
+};
 
 enum PollHandle<T> {
     Ready(Option<T>),
@@ -21,15 +28,25 @@ impl<T> PollHandle<T> {
 
 pub struct JoinHandle<T>(Arc<Mutex<PollHandle<T>>>);
 
+impl<T> JoinHandle<T> {
+    pub fn is_finished(&self) -> bool {
+        let Ok(poll_handle) = self.0.try_lock() else {
+            return false;
+        };
+
+        matches!(*poll_handle, PollHandle::Ready(_))
+    }
+}
+
 impl<T> Future for JoinHandle<T> {
     type Output = T;
 
     fn poll(self: Pin<&mut Self>, _context: &mut Context<'_>) -> Poll<Self::Output> {
         let Ok(mut poll_handle) = self.0.try_lock() else {
-            return Poll::Pending;
+            return pending();
         };
         let PollHandle::Ready(result) = &mut *poll_handle else {
-            return Poll::Pending;
+            return pending();
         };
 
         Poll::Ready(result.take().unwrap())
@@ -48,10 +65,10 @@ where
     queue.send(Box::pin(poll_fn(move |context| {
         let poll_handle = poll_handle_clone.clone();
         let Ok(mut poll_handle) = poll_handle.try_lock() else {
-            return Poll::Pending;
+            return pending();
         };
         let PollHandle::Pending(future) = &mut *poll_handle else {
-            return Poll::Pending;
+            return pending();
         };
         let Poll::Ready(result) = future.as_mut().poll(context) else {
             return Poll::Pending;
@@ -65,6 +82,24 @@ where
     JoinHandle(poll_handle)
 }
 
+pub fn spawn_blocking<T, F>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        sender.send(f()).ok();
+    });
+
+    spawn(poll_fn(move |_context| match receiver.try_recv() {
+        Ok(result) => Poll::Ready(result),
+        Err(TryRecvError::Empty) => pending(),
+        Err(TryRecvError::Disconnected) => panic!("Blocking task panicked"),
+    }))
+}
+
 pub async fn sleep(duration: Duration) {
     sleep_util(Instant::now() + duration).await
 }
@@ -74,7 +109,7 @@ pub async fn sleep_util(instant: Instant) {
         if instant.checked_duration_since(Instant::now()).is_none() {
             Poll::Ready(())
         } else {
-            Poll::Pending
+            pending_until(instant)
         }
     })
     .await;